@@ -1,7 +1,7 @@
 use crate::array::types::*;
 
 use std::fmt;
-use std::ops::{Index, IndexMut};
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
 
 pub struct ArrayBase<S, D>
 where
@@ -28,7 +28,7 @@ macro_rules! array {
     ($($args:tt)*) => {
         {
             let ndarray_array = ndarray::array![$($args)*];
-            crate::array::ArrayBase::new(ndarray_array)
+            $crate::array::ArrayBase::new(ndarray_array)
         }
     };
 }
@@ -55,6 +55,10 @@ where
         self.a.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
     pub fn len_of(&self, axis: Axis) -> usize {
         self.a.len_of(axis)
     }
@@ -87,6 +91,36 @@ where
         self.a.assign(&rhs.a)
     }
 
+    pub fn fill(&mut self, x: A)
+    where
+        S: ndarray::DataMut,
+        A: Clone,
+    {
+        self.a.fill(x)
+    }
+
+    pub fn slice_axis<R>(&self, axis: Axis, range: R) -> ArrayView<'_, A, D>
+    where
+        S: ndarray::Data,
+        R: RangeBounds<usize>,
+    {
+        ArrayView {
+            a: self.a.slice_axis(axis, to_slice(range)),
+        }
+    }
+
+    // copies the subviews at the given indices along axis, in the given order
+    pub fn select(&self, axis: Axis, indices: &[Ix]) -> Array<A, D>
+    where
+        S: ndarray::Data,
+        A: Clone,
+        D: ndarray::RemoveAxis,
+    {
+        Array {
+            a: self.a.select(axis, indices),
+        }
+    }
+
     pub(crate) fn inner_impl(&self) -> &ndarray::ArrayBase<S, D> {
         &self.a
     }
@@ -106,6 +140,22 @@ where
         }
     }
 
+    pub fn column(&self, index: Ix) -> ArrayView<'_, A, Ix1> {
+        ArrayView {
+            a: self.a.column(index),
+        }
+    }
+
+    pub fn slice<R, C>(&self, rows: R, cols: C) -> ArrayView<'_, A, Ix2>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        ArrayView {
+            a: self.a.slice(ndarray::s![to_slice(rows), to_slice(cols)]),
+        }
+    }
+
     pub fn ncols(&self) -> usize {
         self.a.ncols()
     }
@@ -115,6 +165,18 @@ where
     }
 }
 
+impl<A, S> ArrayBase<S, Ix1>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    pub fn slice<R>(&self, range: R) -> ArrayView<'_, A, Ix1>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.slice_axis(Axis(0), range)
+    }
+}
+
 impl<A, S> ArrayBase<S, Ix2>
 where
     S: ndarray::DataMut<Elem = A>,
@@ -166,6 +228,20 @@ where
     }
 }
 
+fn to_slice<R: RangeBounds<usize>>(range: R) -> ndarray::Slice {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start as isize,
+        Bound::Excluded(&start) => start as isize + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Some(end as isize + 1),
+        Bound::Excluded(&end) => Some(end as isize),
+        Bound::Unbounded => None,
+    };
+    ndarray::Slice::new(start, end, 1)
+}
+
 impl<A> ArrayBase<ndarray::OwnedRepr<A>, Ix2> {
     pub fn push_row(&mut self, row: ArrayView<A, Ix1>) -> Result<(), ShapeError>
     where
@@ -313,3 +389,23 @@ where
         self.a.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::array::Axis;
+
+    #[test]
+    fn test_slicing() {
+        let x = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        assert_eq!(x.column(1), array![2.0, 5.0, 8.0]);
+        assert_eq!(x.slice(1.., ..2), array![[4.0, 5.0], [7.0, 8.0]]);
+        assert_eq!(x.slice(..=0, 1..3), array![[2.0, 3.0]]);
+        assert_eq!(x.slice_axis(Axis(1), 2..), array![[3.0], [6.0], [9.0]]);
+        assert_eq!(x.row(2).slice(1..), array![8.0, 9.0]);
+        assert_eq!(
+            x.select(Axis(0), &[2, 0, 2]),
+            array![[7.0, 8.0, 9.0], [1.0, 2.0, 3.0], [7.0, 8.0, 9.0]]
+        );
+        assert_eq!(x.select(Axis(1), &[]).shape(), &[3, 0]);
+    }
+}
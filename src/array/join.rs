@@ -0,0 +1,136 @@
+use crate::array::array_base::*;
+use crate::array::types::*;
+
+pub type SplitView<'a, A, D> = (ArrayView<'a, A, D>, ArrayView<'a, A, D>);
+
+fn out_of_bounds() -> ShapeError {
+    ShapeError::from_kind(ndarray::ErrorKind::OutOfBounds)
+}
+
+// joins arrays along an existing axis, all other axes must have equal length
+pub fn concatenate<A, D>(
+    axis: Axis,
+    arrays: &[ArrayView<'_, A, D>],
+) -> Result<Array<A, D>, ShapeError>
+where
+    A: Clone,
+    D: ndarray::RemoveAxis,
+{
+    let views: Vec<_> = arrays.iter().map(|a| a.inner_impl().view()).collect();
+    Ok(ArrayBase::new(ndarray::concatenate(axis, &views)?))
+}
+
+// joins arrays of the same shape along a new axis inserted at `axis`
+pub fn stack<A, D>(
+    axis: Axis,
+    arrays: &[ArrayView<'_, A, D>],
+) -> Result<Array<A, D::Larger>, ShapeError>
+where
+    A: Clone,
+    D: ndarray::Dimension,
+    D::Larger: ndarray::RemoveAxis,
+{
+    let views: Vec<_> = arrays.iter().map(|a| a.inner_impl().view()).collect();
+    Ok(ArrayBase::new(ndarray::stack(axis, &views)?))
+}
+
+impl<A, S, D> ArrayBase<S, D>
+where
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+{
+    // views of [0, index) and [index, len) along axis
+    pub fn split_at(&self, axis: Axis, index: Ix) -> Result<SplitView<'_, A, D>, ShapeError> {
+        if axis.index() >= self.shape().len() {
+            return Err(out_of_bounds());
+        }
+        if index > self.len_of(axis) {
+            return Err(out_of_bounds());
+        }
+        let (head, tail) = self.inner_impl().view().split_at(axis, index);
+        Ok((ArrayBase::new(head), ArrayBase::new(tail)))
+    }
+}
+
+impl<A, S> ArrayBase<S, Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    // splits the rows before each of the given ascending indices, so n indices
+    // give n + 1 blocks, some of which may be empty
+    pub fn split_rows(&self, indices: &[Ix]) -> Result<Vec<ArrayView<'_, A, Ix2>>, ShapeError> {
+        if indices.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(ShapeError::from_kind(ndarray::ErrorKind::Unsupported));
+        }
+        if indices.last().is_some_and(|&last| last > self.nrows()) {
+            return Err(out_of_bounds());
+        }
+        let mut blocks = Vec::with_capacity(indices.len() + 1);
+        let mut rest = self.inner_impl().view();
+        let mut offset = 0;
+        for &index in indices {
+            let (head, tail) = rest.split_at(Axis(0), index - offset);
+            blocks.push(ArrayBase::new(head));
+            rest = tail;
+            offset = index;
+        }
+        blocks.push(ArrayBase::new(rest));
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+
+    #[test]
+    fn test_concatenate_and_stack() {
+        let x = array![[1.0, 2.0], [3.0, 4.0]];
+        let squares = array![[1.0], [9.0]];
+        assert_eq!(
+            concatenate(Axis(1), &[x.view(), squares.view()]).unwrap(),
+            array![[1.0, 2.0, 1.0], [3.0, 4.0, 9.0]]
+        );
+        assert_eq!(
+            concatenate(Axis(0), &[x.view(), x.row(0).into_shape((1, 2)).unwrap()]).unwrap(),
+            array![[1.0, 2.0], [3.0, 4.0], [1.0, 2.0]]
+        );
+        assert!(concatenate(Axis(0), &[x.view(), squares.view()]).is_err());
+        assert!(concatenate::<f64, Ix2>(Axis(0), &[]).is_err());
+
+        let a = array![1.0, 2.0];
+        let b = array![3.0, 4.0];
+        assert_eq!(
+            stack(Axis(0), &[a.view(), b.view()]).unwrap(),
+            array![[1.0, 2.0], [3.0, 4.0]]
+        );
+        assert_eq!(
+            stack(Axis(1), &[a.view(), b.view()]).unwrap(),
+            array![[1.0, 3.0], [2.0, 4.0]]
+        );
+        assert!(stack(Axis(0), &[a.view(), array![1.0].view()]).is_err());
+    }
+
+    #[test]
+    fn test_split() {
+        let x = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0], [7.0, 8.0]];
+        let (train, validation) = x.split_at(Axis(0), 3).unwrap();
+        assert_eq!(train, array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        assert_eq!(validation, array![[7.0, 8.0]]);
+        let (left, right) = x.split_at(Axis(1), 0).unwrap();
+        assert_eq!(left.shape(), &[4, 0]);
+        assert_eq!(right, x);
+        assert!(x.split_at(Axis(0), 5).is_err());
+        assert!(x.split_at(Axis(2), 0).is_err());
+
+        let blocks = x.split_rows(&[1, 1, 3]).unwrap();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0], array![[1.0, 2.0]]);
+        assert_eq!(blocks[1].nrows(), 0);
+        assert_eq!(blocks[2], array![[3.0, 4.0], [5.0, 6.0]]);
+        assert_eq!(blocks[3], array![[7.0, 8.0]]);
+        assert!(x.split_rows(&[2, 1]).is_err());
+        assert!(x.split_rows(&[5]).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::array::array_base::*;
+use crate::array::types::*;
+use crate::npy::{ByteOrder, Header, NpyElement, NpyError};
+
+use std::fs::File;
+use std::marker::PhantomData;
+use std::path::Path;
+
+// Read-only array backed by a memory-mapped file. Only the pages that are
+// touched get loaded, so datasets larger than RAM can be viewed without copying.
+pub struct MappedArray<A, D> {
+    map: memmap2::Mmap,
+    offset: usize,
+    dim: D,
+    fortran_order: bool,
+    element: PhantomData<A>,
+}
+
+pub type MappedArray1<A> = MappedArray<A, Ix1>;
+pub type MappedArray2<A> = MappedArray<A, Ix2>;
+
+fn incompatible_shapes() -> NpyError {
+    NpyError::ShapeError(ShapeError::from_kind(ndarray::ErrorKind::IncompatibleShape))
+}
+
+// safety: the caller guarantees that the file is not modified while mapped
+unsafe fn map(path: &Path) -> Result<memmap2::Mmap, NpyError> {
+    let file = File::open(path)?;
+    Ok(unsafe { memmap2::Mmap::map(&file)? })
+}
+
+impl<A, D> MappedArray<A, D>
+where
+    A: NpyElement,
+    D: ndarray::Dimension,
+{
+    /// .npy file whose data is in the byte order of this machine, in either C or Fortran order
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other
+    /// process, while the array is alive. Views would otherwise read changed
+    /// or unmapped memory.
+    pub unsafe fn open_npy<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        let map = unsafe { map(path.as_ref())? };
+        let mut reader = &map[..];
+        let header = Header::read(&mut reader)?;
+        let offset = map.len() - reader.len();
+        if header.dtype != A::DTYPE {
+            return Err(NpyError::DtypeMismatch {
+                expected: A::DTYPE.to_string(),
+                found: header.dtype,
+            });
+        }
+        if header.byte_order != ByteOrder::native() {
+            return Err(NpyError::ByteOrderMismatch {
+                expected: ByteOrder::native(),
+                found: header.byte_order,
+            });
+        }
+        header.data_len(A::SIZE)?;
+        let dim =
+            D::from_dimension(&ndarray::IxDyn(&header.shape)).ok_or_else(incompatible_shapes)?;
+        MappedArray::new(map, offset, dim, header.fortran_order)
+    }
+
+    /// headerless file of native endian elements in C order, e.g. written by numpy.ndarray.tofile
+    ///
+    /// # Safety
+    ///
+    /// Same contract as open_npy
+    pub unsafe fn open_raw<P, Sh>(path: P, shape: Sh) -> Result<Self, NpyError>
+    where
+        P: AsRef<Path>,
+        Sh: ndarray::IntoDimension<Dim = D>,
+    {
+        let map = unsafe { map(path.as_ref())? };
+        MappedArray::new(map, 0, shape.into_dimension(), false)
+    }
+
+    fn new(
+        map: memmap2::Mmap,
+        offset: usize,
+        dim: D,
+        fortran_order: bool,
+    ) -> Result<Self, NpyError> {
+        let len = dim
+            .slice()
+            .iter()
+            .try_fold(std::mem::size_of::<A>(), |len, &dimension| {
+                len.checked_mul(dimension)
+            })
+            .ok_or_else(incompatible_shapes)?;
+        if map.len() - offset != len {
+            return Err(incompatible_shapes());
+        }
+        // mappings start on a page boundary, so this only fails for odd offsets
+        if !(map.as_ptr() as usize + offset).is_multiple_of(std::mem::align_of::<A>()) {
+            return Err(NpyError::InvalidHeader("data is not aligned".to_string()));
+        }
+        Ok(MappedArray {
+            map,
+            offset,
+            dim,
+            fortran_order,
+            element: PhantomData,
+        })
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        self.dim.slice()
+    }
+
+    pub fn view(&self) -> ArrayView<'_, A, D> {
+        let bytes = &self.map[self.offset..];
+        // safety: NpyElement is sealed to floats, for which every bit pattern
+        // is valid, the length and alignment were checked on construction, and
+        // the caller of open_npy or open_raw keeps the file unchanged
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                bytes.as_ptr() as *const A,
+                bytes.len() / std::mem::size_of::<A>(),
+            )
+        };
+        let shape = ndarray::ShapeBuilder::set_f(self.dim.clone(), self.fortran_order);
+        ArrayBase::new(
+            ndarray::ArrayView::from_shape(shape, data)
+                .expect("length was checked on construction"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::linalg::Dot;
+    use crate::lms;
+    use crate::npy::write_npy_to;
+
+    use std::io::Write;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ai_playground_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_mapped_npy() {
+        let x = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let path = temp_path("mapped.npy");
+        let mut bytes = Vec::new();
+        write_npy_to(&mut bytes, &x).unwrap();
+        std::fs::write(&path, &bytes).unwrap();
+
+        let mapped = unsafe { MappedArray2::<f64>::open_npy(&path) }.unwrap();
+        assert_eq!(mapped.shape(), &[2, 3]);
+        assert_eq!(mapped.view(), x);
+        let theta = array![1.0, 0.0, -1.0];
+        assert_eq!(mapped.view().dot(&theta).unwrap(), array![-2.0, -2.0]);
+
+        assert!(matches!(
+            unsafe { MappedArray2::<f32>::open_npy(&path) },
+            Err(NpyError::DtypeMismatch { .. })
+        ));
+        assert!(matches!(
+            unsafe { MappedArray1::<f64>::open_npy(&path) },
+            Err(NpyError::ShapeError(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_fortran_and_big_endian() {
+        let path = temp_path("mapped_fortran.npy");
+        let mut file = File::create(&path).unwrap();
+        let mut header = Header {
+            dtype: "f4".to_string(),
+            byte_order: ByteOrder::native(),
+            fortran_order: true,
+            shape: vec![2, 3],
+        };
+        header.write(&mut file).unwrap();
+        for value in [1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0] {
+            file.write_all(&value.to_ne_bytes()).unwrap();
+        }
+        drop(file);
+        let mapped = unsafe { MappedArray2::<f32>::open_npy(&path) }.unwrap();
+        assert_eq!(mapped.view(), array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+        header.byte_order = match ByteOrder::native() {
+            ByteOrder::LittleEndian => ByteOrder::BigEndian,
+            ByteOrder::BigEndian => ByteOrder::LittleEndian,
+        };
+        let mut file = File::create(&path).unwrap();
+        header.write(&mut file).unwrap();
+        file.write_all(&[0u8; 24]).unwrap();
+        drop(file);
+        assert!(matches!(
+            unsafe { MappedArray2::<f32>::open_npy(&path) },
+            Err(NpyError::ByteOrderMismatch { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_raw() {
+        let path = temp_path("mapped.bin");
+        let bytes: Vec<u8> = (0..6).flat_map(|i| (i as f64).to_ne_bytes()).collect();
+        std::fs::write(&path, bytes).unwrap();
+        let mapped = unsafe { MappedArray2::<f64>::open_raw(&path, (3, 2)) }.unwrap();
+        assert_eq!(mapped.view().column(1), array![1.0, 3.0, 5.0]);
+        assert!(unsafe { MappedArray2::<f64>::open_raw(&path, (2, 2)) }.is_err());
+        assert!(unsafe { MappedArray2::<f64>::open_raw(&path, (usize::MAX, 2)) }.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_lms_solve() {
+        let x = array![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0], [1.0, 3.0]];
+        let y = array![1.0, 3.0, 5.0, 7.0];
+        let (x_path, y_path) = (temp_path("lms_x.npy"), temp_path("lms_y.npy"));
+        let mut bytes = Vec::new();
+        write_npy_to(&mut bytes, &x).unwrap();
+        std::fs::write(&x_path, &bytes).unwrap();
+        bytes.clear();
+        write_npy_to(&mut bytes, &y).unwrap();
+        std::fs::write(&y_path, &bytes).unwrap();
+
+        let mapped_x = unsafe { MappedArray2::<f64>::open_npy(&x_path) }.unwrap();
+        let mapped_y = unsafe { MappedArray1::<f64>::open_npy(&y_path) }.unwrap();
+        let solve =
+            |x, y| lms::lms_solve::<f64, lms::BatchKernel, _>(x, y, None, |_| Ok(1.0)).unwrap();
+        let mapped = solve(mapped_x.view(), mapped_y.view());
+        let in_memory = solve(x.view(), y.view());
+        assert_eq!(mapped.theta, in_memory.theta);
+        assert_eq!(mapped.iteration_count, in_memory.iteration_count);
+        std::fs::remove_file(&x_path).unwrap();
+        std::fs::remove_file(&y_path).unwrap();
+    }
+}
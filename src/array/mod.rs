@@ -1,5 +1,12 @@
 pub mod array_base;
+pub mod join;
+pub mod mapped;
+pub mod ops;
+pub mod reduce;
 pub mod types;
 
 pub use crate::array::array_base::*;
+pub use crate::array::join::{concatenate, stack};
+pub use crate::array::mapped::{MappedArray, MappedArray1, MappedArray2};
+pub use crate::array::ops::ScalarOperand;
 pub use crate::array::types::*;
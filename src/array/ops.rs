@@ -0,0 +1,204 @@
+use crate::array::array_base::*;
+use crate::array::types::*;
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+// Element types that may appear as the scalar side of an operator,
+// needed so that `array + scalar` does not overlap with `array + array`
+pub trait ScalarOperand: Copy {}
+
+macro_rules! impl_scalar_operand {
+    ($($t:ty),*) => {
+        $(impl ScalarOperand for $t {})*
+    };
+}
+
+impl_scalar_operand!(f32, f64, i32, i64, u32, u64, isize, usize);
+
+fn incompatible_shapes() -> ShapeError {
+    ShapeError::from_kind(ndarray::ErrorKind::IncompatibleShape)
+}
+
+// NumPy rules: shapes are aligned from the last axis and each pair of lengths
+// must be equal or contain a one
+fn co_broadcast<Output>(lhs: &[usize], rhs: &[usize]) -> Result<Output, ShapeError>
+where
+    Output: ndarray::Dimension,
+{
+    let ndim = lhs.len().max(rhs.len());
+    let mut dim = Output::zeros(ndim);
+    for (i, length) in dim.slice_mut().iter_mut().enumerate() {
+        let l = (i + lhs.len()).checked_sub(ndim).map_or(1, |i| lhs[i]);
+        let r = (i + rhs.len()).checked_sub(ndim).map_or(1, |i| rhs[i]);
+        *length = match (l, r) {
+            (l, r) if l == r => l,
+            (1, r) => r,
+            (l, 1) => l,
+            _ => return Err(incompatible_shapes()),
+        };
+    }
+    Ok(dim)
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $try_assign_method:ident) => {
+        // &array ∘ &array, broadcasting both operands to a common shape
+        impl<'a, 'b, A, S, S2, D, E> $trait<&'b ArrayBase<S2, E>> for &'a ArrayBase<S, D>
+        where
+            A: Clone + $trait<Output = A>,
+            S: ndarray::Data<Elem = A>,
+            S2: ndarray::Data<Elem = A>,
+            D: ndarray::Dimension + ndarray::DimMax<E>,
+            E: ndarray::Dimension,
+        {
+            type Output = Result<Array<A, <D as ndarray::DimMax<E>>::Output>, ShapeError>;
+
+            fn $method(self, rhs: &'b ArrayBase<S2, E>) -> Self::Output {
+                let dim =
+                    co_broadcast::<<D as ndarray::DimMax<E>>::Output>(self.shape(), rhs.shape())?;
+                let lhs = self
+                    .inner_impl()
+                    .broadcast(dim.clone())
+                    .ok_or_else(incompatible_shapes)?;
+                let rhs = rhs
+                    .inner_impl()
+                    .broadcast(dim)
+                    .ok_or_else(incompatible_shapes)?;
+                Ok(ArrayBase::new(
+                    ndarray::Zip::from(&lhs)
+                        .and(&rhs)
+                        .map_collect(|a, b| a.clone().$method(b.clone())),
+                ))
+            }
+        }
+
+        // &array ∘ scalar
+        impl<'a, A, S, D> $trait<A> for &'a ArrayBase<S, D>
+        where
+            A: ScalarOperand + $trait<Output = A>,
+            S: ndarray::Data<Elem = A>,
+            D: ndarray::Dimension,
+        {
+            type Output = Array<A, D>;
+
+            fn $method(self, rhs: A) -> Array<A, D> {
+                ArrayBase::new(self.inner_impl().map(|a| a.$method(rhs)))
+            }
+        }
+
+        // array ∘ scalar, reusing the storage of the owned array
+        impl<A, D> $trait<A> for Array<A, D>
+        where
+            A: ScalarOperand + $trait<Output = A>,
+            D: ndarray::Dimension,
+        {
+            type Output = Array<A, D>;
+
+            fn $method(mut self, rhs: A) -> Array<A, D> {
+                self.inner_impl_mut().mapv_inplace(|a| a.$method(rhs));
+                self
+            }
+        }
+
+        // array ∘= scalar
+        impl<A, S, D> $assign_trait<A> for ArrayBase<S, D>
+        where
+            A: ScalarOperand + $trait<Output = A>,
+            S: ndarray::DataMut<Elem = A>,
+            D: ndarray::Dimension,
+        {
+            fn $assign_method(&mut self, rhs: A) {
+                self.inner_impl_mut().mapv_inplace(|a| a.$method(rhs));
+            }
+        }
+
+        impl<A, S, D> ArrayBase<S, D>
+        where
+            A: Clone + $trait<Output = A>,
+            S: ndarray::DataMut<Elem = A>,
+            D: ndarray::Dimension,
+        {
+            // array ∘= &array with rhs broadcast to the shape of self.
+            // The std assign operators cannot report a shape mismatch, so this form is fallible.
+            pub fn $try_assign_method<S2, E>(
+                &mut self,
+                rhs: &ArrayBase<S2, E>,
+            ) -> Result<(), ShapeError>
+            where
+                S2: ndarray::Data<Elem = A>,
+                E: ndarray::Dimension,
+            {
+                let dim = self.inner_impl().raw_dim();
+                let rhs = rhs
+                    .inner_impl()
+                    .broadcast(dim)
+                    .ok_or_else(incompatible_shapes)?;
+                ndarray::Zip::from(self.inner_impl_mut())
+                    .and(&rhs)
+                    .for_each(|a, b| *a = a.clone().$method(b.clone()));
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign, try_add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign, try_sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign, try_mul_assign);
+impl_binary_op!(Div, div, DivAssign, div_assign, try_div_assign);
+
+#[cfg(test)]
+mod tests {
+    use crate::array;
+
+    #[test]
+    fn test_scalar_operators() {
+        let a = array![[1.0, 2.0], [3.0, 4.0]];
+        assert_eq!(&a + 1.0, array![[2.0, 3.0], [4.0, 5.0]]);
+        assert_eq!(&a * 2.0, array![[2.0, 4.0], [6.0, 8.0]]);
+        assert_eq!(a.clone() - 1.0, array![[0.0, 1.0], [2.0, 3.0]]);
+        let mut b = a.clone();
+        b /= 2.0;
+        assert_eq!(b, array![[0.5, 1.0], [1.5, 2.0]]);
+        let mut first_row = b.row_mut(0);
+        first_row *= 4.0;
+        assert_eq!(b, array![[2.0, 4.0], [1.5, 2.0]]);
+    }
+
+    #[test]
+    fn test_broadcasting_operators() {
+        let x = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let mean = array![2.5, 3.5, 4.5];
+        assert_eq!(
+            (&x - &mean).unwrap(),
+            array![[-1.5, -1.5, -1.5], [1.5, 1.5, 1.5]]
+        );
+        let column = array![[10.0], [20.0]];
+        assert_eq!(
+            (&x * &column).unwrap(),
+            array![[10.0, 20.0, 30.0], [80.0, 100.0, 120.0]]
+        );
+        // (2, 1) and (3,) broadcast to (2, 3)
+        let row = array![1.0, 2.0, 3.0];
+        assert_eq!(
+            (&column + &row).unwrap(),
+            array![[11.0, 12.0, 13.0], [21.0, 22.0, 23.0]]
+        );
+        assert!((&x + &array![1.0, 2.0]).is_err());
+        assert!((&x / &array![[1.0, 2.0, 3.0], [1.0, 2.0, 3.0], [1.0, 2.0, 3.0]]).is_err());
+    }
+
+    #[test]
+    fn test_broadcasting_assign() {
+        let mut x = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        x.try_sub_assign(&array![1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(x, array![[0.0, 0.0, 0.0], [3.0, 3.0, 3.0]]);
+        x.try_div_assign(&array![[1.0], [3.0]]).unwrap();
+        assert_eq!(x, array![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]);
+        // rhs may not grow the shape of self
+        let mut row = array![1.0, 2.0, 3.0];
+        assert!(row.try_add_assign(&x).is_err());
+        assert!(x.try_mul_assign(&array![1.0, 2.0]).is_err());
+        assert_eq!(row, array![1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::array::array_base::*;
+use crate::array::types::*;
+
+use std::cmp::Ordering;
+
+// Picks the element that wins against every other one under `wanted`.
+// Values that are not comparable with themselves (NaN) are skipped unless
+// there is nothing else, so a lane is only `None` when it is empty.
+fn extremum<'a, A, P, I>(iter: I, wanted: Ordering) -> Option<(P, &'a A)>
+where
+    A: PartialOrd + 'a,
+    I: Iterator<Item = (P, &'a A)>,
+{
+    let mut best: Option<(P, &'a A)> = None;
+    for (index, value) in iter {
+        let replace = match &best {
+            None => true,
+            Some(_) if value.partial_cmp(value).is_none() => false,
+            Some((_, current)) => {
+                current.partial_cmp(current).is_none() || value.partial_cmp(current) == Some(wanted)
+            }
+        };
+        if replace {
+            best = Some((index, value));
+        }
+    }
+    best
+}
+
+impl<A, S, D> ArrayBase<S, D>
+where
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+{
+    pub fn sum(&self) -> A
+    where
+        A: Clone + num_traits::Zero,
+    {
+        self.inner_impl().sum()
+    }
+
+    // None for an empty array
+    pub fn mean(&self) -> Option<A>
+    where
+        A: Clone + num_traits::FromPrimitive + num_traits::Zero + std::ops::Div<Output = A>,
+    {
+        self.inner_impl().mean()
+    }
+
+    pub fn sum_axis(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        A: Clone + num_traits::Zero,
+        D: ndarray::RemoveAxis,
+    {
+        ArrayBase::new(self.inner_impl().sum_axis(axis))
+    }
+
+    // None when the axis has length zero
+    pub fn mean_axis(&self, axis: Axis) -> Option<Array<A, D::Smaller>>
+    where
+        A: Clone + num_traits::FromPrimitive + num_traits::Zero + std::ops::Div<Output = A>,
+        D: ndarray::RemoveAxis,
+    {
+        self.inner_impl().mean_axis(axis).map(ArrayBase::new)
+    }
+
+    // variance along axis with `ddof` delta degrees of freedom:
+    // 0 for the population variance, 1 for the sample variance.
+    // None when ddof is negative, NaN or larger than the length of the axis
+    pub fn var_axis(&self, axis: Axis, ddof: A) -> Option<Array<A, D::Smaller>>
+    where
+        A: num_traits::Float + num_traits::FromPrimitive,
+        D: ndarray::RemoveAxis,
+    {
+        self.valid_ddof(axis, ddof)
+            .then(|| ArrayBase::new(self.inner_impl().var_axis(axis, ddof)))
+    }
+
+    pub fn std_axis(&self, axis: Axis, ddof: A) -> Option<Array<A, D::Smaller>>
+    where
+        A: num_traits::Float + num_traits::FromPrimitive,
+        D: ndarray::RemoveAxis,
+    {
+        self.valid_ddof(axis, ddof)
+            .then(|| ArrayBase::new(self.inner_impl().std_axis(axis, ddof)))
+    }
+
+    // ndarray panics outside of 0 ≤ ddof ≤ n
+    fn valid_ddof(&self, axis: Axis, ddof: A) -> bool
+    where
+        A: num_traits::Float + num_traits::FromPrimitive,
+    {
+        let n = A::from_usize(self.inner_impl().len_of(axis));
+        ddof >= A::zero() && n.is_some_and(|n| ddof <= n)
+    }
+
+    pub fn min(&self) -> Option<&A>
+    where
+        A: PartialOrd,
+    {
+        extremum(self.inner_impl().iter().map(|x| ((), x)), Ordering::Less).map(|(_, x)| x)
+    }
+
+    pub fn max(&self) -> Option<&A>
+    where
+        A: PartialOrd,
+    {
+        extremum(self.inner_impl().iter().map(|x| ((), x)), Ordering::Greater).map(|(_, x)| x)
+    }
+
+    // index of the first smallest element
+    pub fn argmin(&self) -> Option<D::Pattern>
+    where
+        A: PartialOrd,
+    {
+        extremum(self.inner_impl().indexed_iter(), Ordering::Less).map(|(i, _)| i)
+    }
+
+    // index of the first largest element
+    pub fn argmax(&self) -> Option<D::Pattern>
+    where
+        A: PartialOrd,
+    {
+        extremum(self.inner_impl().indexed_iter(), Ordering::Greater).map(|(i, _)| i)
+    }
+
+    pub fn min_axis(&self, axis: Axis) -> Option<Array<A, D::Smaller>>
+    where
+        A: PartialOrd + Clone,
+        D: ndarray::RemoveAxis,
+    {
+        self.reduce_lanes(axis, |lane| {
+            extremum(lane.iter().map(|x| ((), x)), Ordering::Less).map(|(_, x)| x.clone())
+        })
+    }
+
+    pub fn max_axis(&self, axis: Axis) -> Option<Array<A, D::Smaller>>
+    where
+        A: PartialOrd + Clone,
+        D: ndarray::RemoveAxis,
+    {
+        self.reduce_lanes(axis, |lane| {
+            extremum(lane.iter().map(|x| ((), x)), Ordering::Greater).map(|(_, x)| x.clone())
+        })
+    }
+
+    pub fn argmin_axis(&self, axis: Axis) -> Option<Array<Ix, D::Smaller>>
+    where
+        A: PartialOrd,
+        D: ndarray::RemoveAxis,
+    {
+        self.reduce_lanes(axis, |lane| {
+            extremum(lane.iter().enumerate(), Ordering::Less).map(|(i, _)| i)
+        })
+    }
+
+    pub fn argmax_axis(&self, axis: Axis) -> Option<Array<Ix, D::Smaller>>
+    where
+        A: PartialOrd,
+        D: ndarray::RemoveAxis,
+    {
+        self.reduce_lanes(axis, |lane| {
+            extremum(lane.iter().enumerate(), Ordering::Greater).map(|(i, _)| i)
+        })
+    }
+
+    // None when the axis has length zero, since every lane is then empty
+    fn reduce_lanes<B, F>(&self, axis: Axis, f: F) -> Option<Array<B, D::Smaller>>
+    where
+        D: ndarray::RemoveAxis,
+        F: Fn(ndarray::ArrayView1<'_, A>) -> Option<B>,
+    {
+        if self.len_of(axis) == 0 {
+            return None;
+        }
+        let reduced = ndarray::Zip::from(self.inner_impl().lanes(axis))
+            .map_collect(|lane| f(lane).expect("lane is not empty"));
+        Some(ArrayBase::new(reduced))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::array;
+    use crate::array::Axis;
+
+    #[test]
+    fn test_axis_reductions() {
+        let x = array![[1.0, 4.0, 3.0], [2.0, 0.0, 6.0]];
+        assert_eq!(x.sum(), 16.0);
+        assert_eq!(x.sum_axis(Axis(0)), array![3.0, 4.0, 9.0]);
+        assert_eq!(x.mean_axis(Axis(1)).unwrap(), array![8.0 / 3.0, 8.0 / 3.0]);
+        assert_eq!(x.var_axis(Axis(0), 0.0).unwrap(), array![0.25, 4.0, 2.25]);
+        assert_eq!(x.var_axis(Axis(0), 1.0).unwrap(), array![0.5, 8.0, 4.5]);
+        assert_eq!(x.std_axis(Axis(0), 0.0).unwrap()[1], 2.0);
+        assert!(x.var_axis(Axis(0), 3.0).is_none());
+        assert!(x.std_axis(Axis(0), -1.0).is_none());
+        assert!(x.var_axis(Axis(0), f64::NAN).is_none());
+        assert_eq!(x.min_axis(Axis(0)).unwrap(), array![1.0, 0.0, 3.0]);
+        assert_eq!(x.max_axis(Axis(1)).unwrap(), array![4.0, 6.0]);
+        assert_eq!(x.argmax_axis(Axis(1)).unwrap(), array![1, 2]);
+        assert_eq!(x.argmin_axis(Axis(0)).unwrap(), array![0, 1, 0]);
+
+        let empty = array::Array2::<f64>::zeros((0, 3));
+        assert!(empty.mean_axis(Axis(0)).is_none());
+        assert!(empty.max_axis(Axis(0)).is_none());
+        assert_eq!(empty.sum_axis(Axis(0)), array![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_extrema() {
+        let x = array![[1.0, 4.0, 3.0], [2.0, 4.0, -6.0]];
+        assert_eq!(x.min(), Some(&-6.0));
+        assert_eq!(x.max(), Some(&4.0));
+        assert_eq!(x.argmin(), Some((1, 2)));
+        // ties resolve to the first occurrence
+        assert_eq!(x.argmax(), Some((0, 1)));
+
+        let with_nan = array![f64::NAN, 2.0, 1.0, f64::NAN];
+        assert_eq!(with_nan.min(), Some(&1.0));
+        assert_eq!(with_nan.argmax(), Some(1));
+        assert!(array![f64::NAN].max().unwrap().is_nan());
+        assert_eq!(array::Array1::<f64>::from_vec(vec![]).argmin(), None);
+    }
+}
@@ -1,5 +1,5 @@
 pub type Ix2 = ndarray::Ix2;
 pub type Ix1 = ndarray::Ix1;
 pub type Ix = ndarray::Ix;
-pub type Axis = ndarray::Axis;
+pub use ndarray::Axis;
 pub type ShapeError = ndarray::ShapeError;
@@ -0,0 +1,317 @@
+use crate::linear_regression::{LinearRegressionError, LinearRegressionModel};
+use crate::random::{self, SplitMix64};
+use crate::training_data::TrainingData;
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Folds {
+    // samples are shuffled with the seed and dealt into k folds of nearly
+    // equal size
+    KFold { k: usize },
+    // k-fold with a different shuffle for every repeat
+    RepeatedKFold { k: usize, repeats: usize },
+    // every sample is the test set of its own fold, no shuffling involved
+    LeaveOneOut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    MeanSquaredError,
+    RootMeanSquaredError,
+    MeanAbsoluteError,
+    // coefficient of determination, undefined for a fold whose targets are
+    // all equal, e.g. with leave-one-out
+    R2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub folds: Folds,
+    pub metric: Metric,
+    pub seed: u64,
+    // keep the model fitted on every fold in CrossValidation::models
+    pub keep_models: bool,
+}
+
+#[derive(Debug)]
+pub enum CrossValidationError {
+    // fewer than 2 folds, more folds than samples or no repeats
+    InvalidFolds {
+        folds: Folds,
+        samples: usize,
+    },
+    UndefinedMetric {
+        metric: Metric,
+        fold: usize,
+    },
+    LinearRegressionError {
+        fold: usize,
+        error: LinearRegressionError,
+    },
+}
+
+impl fmt::Display for CrossValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossValidationError::InvalidFolds { folds, samples } => {
+                write!(f, "cannot make {:?} from {} samples", folds, samples)
+            }
+            CrossValidationError::UndefinedMetric { metric, fold } => {
+                write!(f, "{:?} is undefined for fold {}", metric, fold)
+            }
+            CrossValidationError::LinearRegressionError { fold, error } => {
+                write!(f, "fold {} failed: {}", fold, error)
+            }
+        }
+    }
+}
+
+impl Error for CrossValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CrossValidationError::LinearRegressionError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub struct CrossValidation<T, M> {
+    // one score per fold, repeats one after another
+    pub scores: Vec<T>,
+    pub mean: T,
+    // population standard deviation of the scores
+    pub std: T,
+    pub models: Option<Vec<M>>,
+}
+
+impl Metric {
+    pub fn score<T>(&self, y: &[T], prediction: &[T]) -> Option<T>
+    where
+        T: num_traits::Float,
+    {
+        let n = T::from(y.len())?;
+        let errors = y
+            .iter()
+            .zip(prediction)
+            .map(|(&y, &prediction)| y - prediction);
+        let squared_error = errors.clone().fold(T::zero(), |sum, e| sum + e * e);
+        match self {
+            Metric::MeanSquaredError => Some(squared_error / n),
+            Metric::RootMeanSquaredError => Some((squared_error / n).sqrt()),
+            Metric::MeanAbsoluteError => Some(errors.fold(T::zero(), |sum, e| sum + e.abs()) / n),
+            Metric::R2 => {
+                let mean = y.iter().fold(T::zero(), |sum, &y| sum + y) / n;
+                let total = y
+                    .iter()
+                    .fold(T::zero(), |sum, &y| sum + (y - mean) * (y - mean));
+                (total > T::zero()).then(|| T::one() - squared_error / total)
+            }
+        }
+    }
+}
+
+impl Folds {
+    // test indices of every fold
+    fn test_sets(
+        &self,
+        samples: usize,
+        seed: u64,
+    ) -> Result<Vec<Vec<usize>>, CrossValidationError> {
+        let (k, repeats) = match *self {
+            Folds::KFold { k } => (k, 1),
+            Folds::RepeatedKFold { k, repeats } => (k, repeats),
+            Folds::LeaveOneOut => (samples, 1),
+        };
+        if k < 2 || k > samples || repeats == 0 {
+            return Err(CrossValidationError::InvalidFolds {
+                folds: *self,
+                samples,
+            });
+        }
+        if *self == Folds::LeaveOneOut {
+            return Ok((0..samples).map(|i| vec![i]).collect());
+        }
+        let mut seeds = SplitMix64::new(seed);
+        let mut test_sets = Vec::with_capacity(k * repeats);
+        for _ in 0..repeats {
+            let indices = random::permutation(samples, seeds.next_u64());
+            // fold sizes differ by at most one sample
+            test_sets
+                .extend((0..k).map(|i| indices[i * samples / k..(i + 1) * samples / k].to_vec()));
+        }
+        Ok(test_sets)
+    }
+}
+
+// Fits a model with fit on the training part of every fold and scores its
+// predictions of the held out part. fit is a model constructor, e.g.
+// |data| GradientDescent::fit::<BatchKernel>(data, &settings)
+pub fn cross_validate<T, M, F>(
+    data: &TrainingData<T>,
+    settings: &Settings,
+    mut fit: F,
+) -> Result<CrossValidation<T, M>, CrossValidationError>
+where
+    T: num_traits::Float,
+    M: LinearRegressionModel<T>,
+    F: FnMut(&TrainingData<T>) -> Result<M, LinearRegressionError>,
+{
+    let samples = data.y.len();
+    let test_sets = settings.folds.test_sets(samples, settings.seed)?;
+    let mut scores = Vec::with_capacity(test_sets.len());
+    let mut models = settings.keep_models.then(Vec::new);
+    let mut in_test = vec![false; samples];
+    for (fold, test) in test_sets.iter().enumerate() {
+        let model_error = |error| CrossValidationError::LinearRegressionError { fold, error };
+        test.iter().for_each(|&i| in_test[i] = true);
+        let train: Vec<usize> = (0..samples).filter(|&i| !in_test[i]).collect();
+        test.iter().for_each(|&i| in_test[i] = false);
+
+        let model = fit(&data.select_rows(&train)).map_err(model_error)?;
+        let test = data.select_rows(test);
+        let prediction = model.predict_batch(&test.x.view()).map_err(model_error)?;
+        let y: Vec<T> = test.y.into_iter().copied().collect();
+        let prediction: Vec<T> = prediction.into_iter().copied().collect();
+        let score = settings.metric.score(&y, &prediction).ok_or(
+            CrossValidationError::UndefinedMetric {
+                metric: settings.metric,
+                fold,
+            },
+        )?;
+        scores.push(score);
+        if let Some(models) = &mut models {
+            models.push(model);
+        }
+    }
+
+    let count = T::from(scores.len()).ok_or(CrossValidationError::InvalidFolds {
+        folds: settings.folds,
+        samples,
+    })?;
+    let mean = scores.iter().fold(T::zero(), |sum, &score| sum + score) / count;
+    let variance = scores.iter().fold(T::zero(), |sum, &score| {
+        sum + (score - mean) * (score - mean)
+    }) / count;
+    Ok(CrossValidation {
+        scores,
+        mean,
+        std: variance.sqrt(),
+        models,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::normal_equation::NormalEquation;
+    use crate::parametric_algorithm::ParametricAlgorithm;
+
+    fn line(n: usize) -> TrainingData<f64> {
+        let x: Vec<f64> = (0..n).flat_map(|i| [1.0, i as f64]).collect();
+        let y = (0..n).map(|i| 2.0 + 3.0 * i as f64).collect();
+        TrainingData::new(
+            array::Array2::from_shape_vec((n, 2), x).unwrap(),
+            array::Array1::from_vec(y),
+        )
+    }
+
+    #[test]
+    fn test_folds() {
+        let test_sets = Folds::KFold { k: 3 }.test_sets(10, 5).unwrap();
+        let sizes: Vec<usize> = test_sets.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![3, 3, 4]);
+        let mut all: Vec<usize> = test_sets.concat();
+        all.sort();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+
+        let repeated = Folds::RepeatedKFold { k: 3, repeats: 2 }
+            .test_sets(10, 5)
+            .unwrap();
+        assert_eq!(repeated.len(), 6);
+        assert_ne!(repeated[..3], repeated[3..]);
+        assert_eq!(
+            repeated,
+            Folds::RepeatedKFold { k: 3, repeats: 2 }
+                .test_sets(10, 5)
+                .unwrap()
+        );
+        assert_eq!(
+            Folds::LeaveOneOut.test_sets(3, 5).unwrap(),
+            vec![vec![0], vec![1], vec![2]]
+        );
+        assert!(matches!(
+            Folds::KFold { k: 11 }.test_sets(10, 5),
+            Err(CrossValidationError::InvalidFolds { samples: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cross_validate() {
+        let data = line(12);
+        let settings = Settings {
+            folds: Folds::KFold { k: 4 },
+            metric: Metric::RootMeanSquaredError,
+            seed: 1,
+            keep_models: true,
+        };
+        let result = cross_validate(&data, &settings, NormalEquation::new).unwrap();
+        assert_eq!(result.scores.len(), 4);
+        assert!(result.mean < 1e-9 && result.std < 1e-9);
+        let models = result.models.unwrap();
+        assert_eq!(models.len(), 4);
+        assert!((models[0].theta()[1] - 3.0).abs() < 1e-9);
+
+        // a model that always predicts 0 has an error of the mean of |y|
+        let settings = Settings {
+            folds: Folds::LeaveOneOut,
+            metric: Metric::MeanAbsoluteError,
+            keep_models: false,
+            ..settings
+        };
+        let mut zero = line(12);
+        zero.x.fill(0.0);
+        let result = cross_validate(&zero, &settings, NormalEquation::new).unwrap();
+        assert!(result.models.is_none());
+        assert_eq!(
+            result.scores,
+            data.y.into_iter().copied().collect::<Vec<_>>()
+        );
+        assert!((result.mean - 18.5).abs() < 1e-12);
+
+        let settings = Settings {
+            metric: Metric::R2,
+            ..settings
+        };
+        assert!(matches!(
+            cross_validate(&data, &settings, NormalEquation::new),
+            Err(CrossValidationError::UndefinedMetric { fold: 0, .. })
+        ));
+
+        let error = cross_validate::<f64, NormalEquation<f64>, _>(&data, &settings, |_| {
+            Err(LinearRegressionError::EmptyDataset)
+        })
+        .err()
+        .unwrap();
+        assert_eq!(error.to_string(), "fold 0 failed: no samples to train on");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_metric() {
+        let y = [1.0, 2.0, 3.0];
+        let prediction = [1.0, 2.0, 5.0];
+        assert_eq!(
+            Metric::MeanSquaredError.score(&y, &prediction),
+            Some(4.0 / 3.0)
+        );
+        assert_eq!(
+            Metric::MeanAbsoluteError.score(&y, &prediction),
+            Some(2.0 / 3.0)
+        );
+        assert_eq!(Metric::R2.score(&y, &prediction), Some(-1.0));
+        assert_eq!(Metric::R2.score(&[1.0], &[1.0]), None);
+    }
+}
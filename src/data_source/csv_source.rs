@@ -0,0 +1,308 @@
+use crate::data_source::{Batch, BatchBuffer, DataSource};
+use crate::training_data::{self, ReadOptions, TrainingDataError, INTERCEPT};
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// The file under the csv reader, keeping the bytes read since the current
+// record so that errors can find its line without reading the file again.
+// csv only reports where the search for a record started, which can be
+// before blank and comment lines.
+struct LineTracker {
+    file: File,
+    bytes: Vec<u8>,
+    // offset in the file of bytes[0]
+    start: u64,
+}
+
+impl LineTracker {
+    fn new(file: File) -> Self {
+        Self {
+            file,
+            bytes: Vec::new(),
+            start: 0,
+        }
+    }
+
+    // drops the bytes before offset once they make up half of the buffer,
+    // so that moving the rest is paid for by the bytes dropped
+    fn forget_before(&mut self, offset: u64) {
+        let count = (offset.saturating_sub(self.start) as usize).min(self.bytes.len());
+        if count > 0 && count >= self.bytes.len() / 2 {
+            self.bytes.drain(..count);
+            self.start += count as u64;
+        }
+    }
+
+    fn from(&self, offset: u64) -> &[u8] {
+        let index = offset.saturating_sub(self.start) as usize;
+        self.bytes.get(index..).unwrap_or_default()
+    }
+}
+
+impl Read for LineTracker {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.file.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+}
+
+impl Seek for LineTracker {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        self.start = self.file.seek(position)?;
+        self.bytes.clear();
+        Ok(self.start)
+    }
+}
+
+// Reads a csv file one record at a time, with the column selection, header
+// detection and error locations of read_data_with. Imputation and categorical
+// columns need statistics of the whole file, so a missing value is an error.
+pub struct CsvSource<T> {
+    path: PathBuf,
+    reader: csv::Reader<LineTracker>,
+    record: csv::StringRecord,
+    has_header: bool,
+    comment: Option<u8>,
+    missing_values: Vec<String>,
+    intercept: bool,
+    header: Vec<String>,
+    target: usize,
+    features: Vec<usize>,
+    feature_names: Vec<String>,
+    buffer: BatchBuffer<T>,
+    // features of the record being read, kept to reuse its allocation
+    row: Vec<T>,
+}
+
+impl<T> CsvSource<T>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    pub fn open(path: impl AsRef<Path>, options: &ReadOptions) -> Result<Self, TrainingDataError> {
+        let path = path.as_ref().to_path_buf();
+        let unsupported = [
+            ("imputation", !options.imputation.is_empty()),
+            ("default_imputation", options.default_imputation.is_some()),
+            ("categorical", !options.categorical.is_empty()),
+            ("detect_categorical", options.detect_categorical.is_some()),
+        ];
+        if let Some(&(option, _)) = unsupported.iter().find(|(_, used)| *used) {
+            return Err(TrainingDataError::UnsupportedOption { path, option });
+        }
+        let file = File::open(&path).map_err(|source| TrainingDataError::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        let mut reader = training_data::csv_reader(LineTracker::new(file), options);
+        let mut record = csv::StringRecord::new();
+        let read = reader
+            .read_record(&mut record)
+            .map_err(|error| TrainingDataError::from_csv(&path, error))?;
+        if !read {
+            return Err(TrainingDataError::EmptyDataset { path });
+        }
+        let has_header = training_data::has_header::<T>(record.iter().map(Some), options);
+        let header = if has_header {
+            record.iter().map(|name| name.trim().to_string()).collect()
+        } else {
+            training_data::unnamed_columns(record.len())
+        };
+        let (target, features) =
+            training_data::select_columns(&header, options).map_err(|error| error.at(&path))?;
+        let feature_names: Vec<String> = options
+            .intercept
+            .then(|| INTERCEPT.to_string())
+            .into_iter()
+            .chain(features.iter().map(|&column| header[column].clone()))
+            .collect();
+
+        let mut source = Self {
+            path,
+            reader,
+            record,
+            has_header,
+            comment: options.comment,
+            missing_values: options.missing_values.clone(),
+            intercept: options.intercept,
+            header,
+            target,
+            features,
+            buffer: BatchBuffer::new(feature_names.len()),
+            row: Vec::with_capacity(feature_names.len()),
+            feature_names,
+        };
+        source.rewind()?;
+        if source.next_batch(1)?.is_none() {
+            return Err(TrainingDataError::EmptyDataset { path: source.path });
+        }
+        source.rewind()?;
+        Ok(source)
+    }
+
+    pub fn target_name(&self) -> &str {
+        &self.header[self.target]
+    }
+
+    // only needed for errors, so the blank and comment lines that the csv
+    // reader skipped are counted on demand
+    fn line(&self) -> u64 {
+        let Some(position) = self.record.position() else {
+            return 0;
+        };
+        let rest = self.reader.get_ref().from(position.byte());
+        training_data::record_line(rest, position, self.comment)
+    }
+
+    fn parse(&self, column: usize) -> Result<T, TrainingDataError> {
+        let text = self.record[column].trim();
+        if self.missing_values.iter().any(|marker| marker == text) {
+            return Err(TrainingDataError::MissingValue {
+                path: self.path.clone(),
+                line: self.line(),
+                column: self.header[column].clone(),
+            });
+        }
+        text.parse::<T>()
+            .map_err(|source| TrainingDataError::Parse {
+                path: self.path.clone(),
+                line: self.line(),
+                column: self.header[column].clone(),
+                text: self.record[column].to_string(),
+                source: Box::new(source),
+            })
+    }
+}
+
+impl<T> DataSource<T> for CsvSource<T>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    type Error = TrainingDataError;
+
+    fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    fn next_batch(&mut self, max_rows: usize) -> Result<Option<Batch<'_, T>>, Self::Error> {
+        self.buffer.clear();
+        for _ in 0..max_rows {
+            let read = self
+                .reader
+                .read_record(&mut self.record)
+                .map_err(|error| TrainingDataError::from_csv(&self.path, error))?;
+            if !read {
+                break;
+            }
+            if let Some(position) = self.record.position() {
+                self.reader.get_mut().forget_before(position.byte());
+            }
+            self.row.clear();
+            if self.intercept {
+                self.row.push(T::one());
+            }
+            for &column in &self.features {
+                self.row.push(self.parse(column)?);
+            }
+            let y = self.parse(self.target)?;
+            self.buffer.push(self.row.drain(..), y);
+        }
+        Ok(self.buffer.batch())
+    }
+
+    fn rewind(&mut self) -> Result<(), Self::Error> {
+        self.reader
+            .seek(csv::Position::new())
+            .map_err(|error| TrainingDataError::from_csv(&self.path, error))?;
+        if self.has_header {
+            self.reader
+                .read_record(&mut self.record)
+                .map_err(|error| TrainingDataError::from_csv(&self.path, error))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+
+    #[test]
+    fn test_csv_source() {
+        let options = ReadOptions {
+            target: Some("x_1".into()),
+            ..ReadOptions::default()
+        };
+        let data = training_data::read_data_with::<f64>("resources/4.csv", &options).unwrap();
+        let mut source = CsvSource::<f64>::open("resources/4.csv", &options).unwrap();
+        assert_eq!(source.feature_names(), data.feature_names.as_slice());
+        assert_eq!(source.target_name(), "x_1");
+        for _ in 0..2 {
+            let mut rows = 0;
+            while let Some(batch) = source.next_batch(7).unwrap() {
+                assert!(batch.y.len() <= 7);
+                let end = rows + batch.y.len();
+                assert_eq!(batch.x, data.x.slice(rows..end, ..));
+                assert_eq!(batch.y, data.y.slice(rows..end));
+                rows = end;
+            }
+            assert_eq!(rows, data.y.len());
+            source.rewind().unwrap();
+        }
+
+        // a headerless file keeps its first sample
+        let mut source =
+            CsvSource::<f64>::open("resources/3.csv", &ReadOptions::default()).unwrap();
+        let batch = source.next_batch(1).unwrap().unwrap();
+        assert_eq!(batch.x, array![[1.0, 1.2000000000000002]]);
+    }
+
+    #[test]
+    fn test_csv_source_errors() {
+        let path = std::env::temp_dir().join(format!(
+            "ai_playground_{}_streaming.csv",
+            std::process::id()
+        ));
+        std::fs::write(&path, "x_1,y\n1.0,2.0\n\n# note\n3.0,abc\n").unwrap();
+        let options = ReadOptions {
+            comment: Some(b'#'),
+            ..ReadOptions::default()
+        };
+        let mut source = CsvSource::<f64>::open(&path, &options).unwrap();
+        assert!(source.next_batch(1).unwrap().is_some());
+        assert!(matches!(
+            source.next_batch(1),
+            Err(TrainingDataError::Parse { line: 5, .. })
+        ));
+
+        // the error is far beyond the bytes the csv reader buffers at once
+        let rows = "1.0,2.0\n".repeat(2000);
+        std::fs::write(&path, format!("x_1,y\n{}\n# note\n3.0,abc\n", rows)).unwrap();
+        let mut source = CsvSource::<f64>::open(&path, &options).unwrap();
+        assert_eq!(source.next_batch(2000).unwrap().unwrap().y.len(), 2000);
+        assert!(matches!(
+            source.next_batch(1),
+            Err(TrainingDataError::Parse { line: 2004, .. })
+        ));
+
+        let options = ReadOptions {
+            default_imputation: Some(crate::preprocessing::ImputeStrategy::Mean),
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            CsvSource::<f64>::open(&path, &options),
+            Err(TrainingDataError::UnsupportedOption {
+                option: "default_imputation",
+                ..
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
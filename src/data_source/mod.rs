@@ -0,0 +1,152 @@
+pub mod csv_source;
+pub mod npy_source;
+
+pub use crate::data_source::csv_source::CsvSource;
+pub use crate::data_source::npy_source::NpySource;
+
+use crate::array;
+use crate::training_data::{self, TrainingData};
+
+use std::error::Error;
+
+// consecutive samples of a data source, borrowed until the next call
+pub struct Batch<'a, T> {
+    pub x: array::ArrayView2<'a, T>,
+    pub y: array::ArrayView1<'a, T>,
+}
+
+// Samples read in order, a few at a time, so that training does not need the
+// whole dataset in memory. Every pass over the data starts with rewind.
+pub trait DataSource<T> {
+    type Error: Error + Send + Sync + 'static;
+
+    // one name per column of x
+    fn feature_names(&self) -> &[String];
+
+    // the next at most max_rows samples, None once every sample was read
+    fn next_batch(&mut self, max_rows: usize) -> Result<Option<Batch<'_, T>>, Self::Error>;
+
+    // starts over from the first sample
+    fn rewind(&mut self) -> Result<(), Self::Error>;
+}
+
+// Serves arrays that are already in memory without copying them.
+pub struct ArraySource<'a, T> {
+    x: array::ArrayView2<'a, T>,
+    y: array::ArrayView1<'a, T>,
+    feature_names: Vec<String>,
+    position: usize,
+}
+
+impl<'a, T> ArraySource<'a, T> {
+    // names the features like TrainingData::new
+    pub fn new(x: array::ArrayView2<'a, T>, y: array::ArrayView1<'a, T>) -> Self
+    where
+        T: num_traits::One + PartialEq,
+    {
+        let feature_names = training_data::default_feature_names(&x);
+        Self {
+            x,
+            y,
+            feature_names,
+            position: 0,
+        }
+    }
+
+    pub fn from_training_data(data: &'a TrainingData<T>) -> Self {
+        Self {
+            x: data.x.view(),
+            y: data.y.view(),
+            feature_names: data.feature_names.clone(),
+            position: 0,
+        }
+    }
+}
+
+impl<T> DataSource<T> for ArraySource<'_, T> {
+    type Error = std::convert::Infallible;
+
+    fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    fn next_batch(&mut self, max_rows: usize) -> Result<Option<Batch<'_, T>>, Self::Error> {
+        let start = self.position;
+        let end = self.x.nrows().min(start + max_rows);
+        if start >= end {
+            return Ok(None);
+        }
+        self.position = end;
+        Ok(Some(Batch {
+            x: self.x.slice(start..end, ..),
+            y: self.y.slice(start..end),
+        }))
+    }
+
+    fn rewind(&mut self) -> Result<(), Self::Error> {
+        self.position = 0;
+        Ok(())
+    }
+}
+
+// reusable storage for the batches of sources that parse their samples
+pub(crate) struct BatchBuffer<T> {
+    ncols: usize,
+    x: Vec<T>,
+    y: Vec<T>,
+}
+
+impl<T> BatchBuffer<T> {
+    pub(crate) fn new(ncols: usize) -> Self {
+        Self {
+            ncols,
+            x: Vec::new(),
+            y: Vec::new(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.x.clear();
+        self.y.clear();
+    }
+
+    // appends one sample, row holds ncols values
+    pub(crate) fn push(&mut self, row: impl IntoIterator<Item = T>, y: T) {
+        self.x.extend(row);
+        self.y.push(y);
+    }
+
+    pub(crate) fn batch(&self) -> Option<Batch<'_, T>> {
+        if self.y.is_empty() {
+            return None;
+        }
+        let x = ndarray::ArrayView2::from_shape((self.y.len(), self.ncols), &self.x)
+            .expect("every row holds ncols values");
+        Some(Batch {
+            x: array::ArrayBase::new(x),
+            y: array::ArrayBase::new(ndarray::ArrayView1::from(&self.y)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_source() {
+        let x = array![[1.0, 2.0], [1.0, 3.0], [1.0, 4.0]];
+        let y = array![5.0, 6.0, 7.0];
+        let mut source = ArraySource::new(x.view(), y.view());
+        assert_eq!(source.feature_names(), &[training_data::INTERCEPT, "x_1"]);
+        for _ in 0..2 {
+            let batch = source.next_batch(2).unwrap().unwrap();
+            assert_eq!(batch.x, array![[1.0, 2.0], [1.0, 3.0]]);
+            assert_eq!(batch.y, array![5.0, 6.0]);
+            let batch = source.next_batch(2).unwrap().unwrap();
+            assert_eq!(batch.y, array![7.0]);
+            assert!(source.next_batch(2).unwrap().is_none());
+            source.rewind().unwrap();
+        }
+    }
+}
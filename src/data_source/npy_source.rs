@@ -0,0 +1,180 @@
+use crate::data_source::{Batch, BatchBuffer, DataSource};
+use crate::npy::{Header, NpyElement, NpyError};
+use crate::training_data::INTERCEPT;
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+// .npy file read from the start of its data one row at a time
+struct NpyStream {
+    reader: BufReader<File>,
+    header: Header,
+    data_start: u64,
+}
+
+impl NpyStream {
+    fn open<A: NpyElement>(path: &Path, dimensions: usize) -> Result<Self, NpyError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let header = Header::read(&mut reader)?;
+        if header.dtype != A::DTYPE {
+            return Err(NpyError::DtypeMismatch {
+                expected: A::DTYPE.to_string(),
+                found: header.dtype,
+            });
+        }
+        if header.shape.len() != dimensions {
+            return Err(NpyError::ShapeError(ndarray::ShapeError::from_kind(
+                ndarray::ErrorKind::IncompatibleShape,
+            )));
+        }
+        // rows of column major data are not contiguous
+        if header.fortran_order && dimensions > 1 {
+            return Err(NpyError::InvalidHeader(
+                "fortran order data cannot be read by rows".to_string(),
+            ));
+        }
+        let data_start = reader.stream_position()?;
+        Ok(Self {
+            reader,
+            header,
+            data_start,
+        })
+    }
+
+    // the next count elements, decoded from bytes
+    fn read<'a, A: NpyElement>(
+        &mut self,
+        count: usize,
+        bytes: &'a mut Vec<u8>,
+    ) -> Result<impl Iterator<Item = A> + 'a, NpyError> {
+        bytes.resize(count * A::SIZE, 0);
+        self.reader.read_exact(bytes)?;
+        let byte_order = self.header.byte_order;
+        Ok(bytes
+            .chunks_exact(A::SIZE)
+            .map(move |chunk| A::from_bytes(chunk, byte_order)))
+    }
+}
+
+// Streams the rows of a 2 dimensional x.npy together with the elements of a
+// 1 dimensional y.npy, e.g. as saved by numpy.save.
+pub struct NpySource<A> {
+    x: NpyStream,
+    y: NpyStream,
+    intercept: bool,
+    rows: usize,
+    position: usize,
+    feature_names: Vec<String>,
+    bytes: Vec<u8>,
+    buffer: BatchBuffer<A>,
+}
+
+impl<A> NpySource<A>
+where
+    A: NpyElement + num_traits::Float,
+{
+    // features are named x_1, x_2, ... after the optional intercept
+    pub fn open(
+        x_path: impl AsRef<Path>,
+        y_path: impl AsRef<Path>,
+        intercept: bool,
+    ) -> Result<Self, NpyError> {
+        let x = NpyStream::open::<A>(x_path.as_ref(), 2)?;
+        let y = NpyStream::open::<A>(y_path.as_ref(), 1)?;
+        let rows = x.header.shape[0];
+        if y.header.shape[0] != rows {
+            return Err(NpyError::ShapeError(ndarray::ShapeError::from_kind(
+                ndarray::ErrorKind::IncompatibleShape,
+            )));
+        }
+        let feature_names: Vec<String> = intercept
+            .then(|| INTERCEPT.to_string())
+            .into_iter()
+            .chain((1..=x.header.shape[1]).map(|i| format!("x_{}", i)))
+            .collect();
+        Ok(Self {
+            x,
+            y,
+            intercept,
+            rows,
+            position: 0,
+            buffer: BatchBuffer::new(feature_names.len()),
+            feature_names,
+            bytes: Vec::new(),
+        })
+    }
+}
+
+impl<A> DataSource<A> for NpySource<A>
+where
+    A: NpyElement + num_traits::Float,
+{
+    type Error = NpyError;
+
+    fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    fn next_batch(&mut self, max_rows: usize) -> Result<Option<Batch<'_, A>>, Self::Error> {
+        self.buffer.clear();
+        let count = max_rows.min(self.rows - self.position);
+        let ncols = self.x.header.shape[1];
+        let intercept = self.intercept.then(A::one);
+        for _ in 0..count {
+            let y = self.y.read::<A>(1, &mut self.bytes)?.next();
+            let row = self.x.read::<A>(ncols, &mut self.bytes)?;
+            self.buffer
+                .push(intercept.into_iter().chain(row), y.unwrap_or_else(A::nan));
+        }
+        self.position += count;
+        Ok(self.buffer.batch())
+    }
+
+    fn rewind(&mut self) -> Result<(), Self::Error> {
+        self.x.reader.seek(SeekFrom::Start(self.x.data_start))?;
+        self.y.reader.seek(SeekFrom::Start(self.y.data_start))?;
+        self.position = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::npy::write_npy;
+
+    #[test]
+    fn test_npy_source() {
+        let temp_path = |name: &str| {
+            std::env::temp_dir().join(format!("ai_playground_{}_{}", std::process::id(), name))
+        };
+        let (x_path, y_path) = (temp_path("source_x.npy"), temp_path("source_y.npy"));
+        write_npy(&x_path, &array![[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]).unwrap();
+        write_npy(&y_path, &array![7.0f32, 8.0, 9.0]).unwrap();
+
+        let mut source = NpySource::<f32>::open(&x_path, &y_path, true).unwrap();
+        assert_eq!(source.feature_names(), &[INTERCEPT, "x_1", "x_2"]);
+        for _ in 0..2 {
+            let batch = source.next_batch(2).unwrap().unwrap();
+            assert_eq!(batch.x, array![[1.0, 1.0, 2.0], [1.0, 3.0, 4.0]]);
+            assert_eq!(batch.y, array![7.0, 8.0]);
+            let batch = source.next_batch(2).unwrap().unwrap();
+            assert_eq!(batch.x, array![[1.0, 5.0, 6.0]]);
+            assert!(source.next_batch(2).unwrap().is_none());
+            source.rewind().unwrap();
+        }
+
+        assert!(matches!(
+            NpySource::<f64>::open(&x_path, &y_path, true),
+            Err(NpyError::DtypeMismatch { .. })
+        ));
+        assert!(matches!(
+            NpySource::<f32>::open(&y_path, &x_path, true),
+            Err(NpyError::ShapeError(_))
+        ));
+        std::fs::remove_file(&x_path).unwrap();
+        std::fs::remove_file(&y_path).unwrap();
+    }
+}
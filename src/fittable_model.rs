@@ -12,6 +12,8 @@ pub struct FittingSettings<T> {
     pub learning_rate: T,
     pub eps: T,
     pub starting_theta: array::Array1<T>,
+    // samples per update of the mini-batch kernel
+    pub batch_size: usize,
 }
 
 pub trait FittableModel<T>: parametric_algorithm::ParametricAlgorithm<T> {
@@ -1,4 +1,5 @@
 use crate::array;
+use crate::data_source::DataSource;
 use crate::fittable_model;
 use crate::linalg::dot::Dot;
 use crate::linear_regression;
@@ -9,6 +10,7 @@ use crate::training_data;
 
 pub struct GradientDescent<T> {
     theta: array::Array1<T>,
+    feature_names: Vec<String>,
     iteration_count: usize,
 }
 
@@ -16,6 +18,10 @@ impl From<lms::LMSError> for LinearRegressionError {
     fn from(error: lms::LMSError) -> Self {
         match error {
             lms::LMSError::FailedCastToT => LinearRegressionError::FailedCastToT,
+            lms::LMSError::InvalidBatchSize => LinearRegressionError::InvalidBatchSize,
+            lms::LMSError::EmptyDataset => LinearRegressionError::EmptyDataset,
+            lms::LMSError::LinalgError(error) => LinearRegressionError::LinalgError(error),
+            lms::LMSError::DataSourceError(error) => LinearRegressionError::DataSourceError(error),
         }
     }
 }
@@ -25,7 +31,14 @@ where
     T: num_traits::Float + std::iter::Sum,
 {
     fn predict(&self, x: &array::ArrayView1<T>) -> Result<T, LinearRegressionError> {
-        Ok(x.dot(&self.theta))
+        Ok(x.dot(&self.theta)?)
+    }
+
+    fn predict_batch(
+        &self,
+        x: &array::ArrayView2<T>,
+    ) -> Result<array::Array1<T>, LinearRegressionError> {
+        Ok(x.dot(&self.theta)?)
     }
 }
 
@@ -33,11 +46,53 @@ impl<T> parametric_algorithm::ParametricAlgorithm<T> for GradientDescent<T>
 where
     T: num_traits::Float + std::iter::Sum,
 {
-    fn theta(&self) -> array::ArrayView1<T> {
+    fn theta(&self) -> array::ArrayView1<'_, T> {
         self.theta.view()
     }
 }
 
+impl<T> parametric_algorithm::FeatureNames for GradientDescent<T> {
+    fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+}
+
+fn lms_settings<T: Copy>(settings: &fittable_model::FittingSettings<T>) -> lms::LMSSettings<T> {
+    lms::LMSSettings {
+        max_iteration_count: Some(settings.max_iteration_count),
+        learning_rate: Some(settings.learning_rate),
+        eps: Some(settings.eps),
+        starting_theta: Some(settings.starting_theta.clone()),
+        batch_size: Some(settings.batch_size),
+    }
+}
+
+impl<T> GradientDescent<T>
+where
+    T: num_traits::Float + num_traits::NumAssignOps + std::iter::Sum,
+{
+    // trains from a source that is read again for every pass instead of being
+    // held in memory, the features are named after the source
+    pub fn fit_streaming<K, D>(
+        source: &mut D,
+        settings: &fittable_model::FittingSettings<T>,
+    ) -> Result<Self, LinearRegressionError>
+    where
+        K: lms::StreamingKernel<T>,
+        D: DataSource<T>,
+    {
+        let lms_result =
+            lms::lms_solve_streaming::<T, K, D, _>(source, Some(lms_settings(settings)), |_| {
+                Ok(T::one())
+            })?;
+        Ok(Self {
+            theta: lms_result.theta,
+            feature_names: source.feature_names().to_vec(),
+            iteration_count: lms_result.iteration_count,
+        })
+    }
+}
+
 impl<T> fittable_model::FittableModel<T> for GradientDescent<T>
 where
     T: num_traits::Float + num_traits::NumAssignOps + std::iter::Sum,
@@ -49,20 +104,15 @@ where
     where
         K: lms::Kernel<T>,
     {
-        let settings = lms::LMSSettings {
-            max_iteration_count: Some(settings.max_iteration_count),
-            learning_rate: Some(settings.learning_rate),
-            eps: Some(settings.eps),
-            starting_theta: Some(settings.starting_theta.clone()),
-        };
         let lms_result = lms::lms_solve::<T, K, _>(
             training_data.x.view(),
             training_data.y.view(),
-            Some(settings),
-            |_| T::one(),
+            Some(lms_settings(settings)),
+            |_| Ok(T::one()),
         )?;
         Ok(Self {
             theta: lms_result.theta,
+            feature_names: training_data.feature_names.clone(),
             iteration_count: lms_result.iteration_count,
         })
     }
@@ -73,3 +123,69 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_source::{ArraySource, CsvSource};
+    use crate::fittable_model::FittableModel;
+    use crate::parametric_algorithm::{FeatureNames, ParametricAlgorithm};
+    use crate::training_data::{read_data, ReadOptions};
+
+    #[test]
+    fn test_fit_streaming() {
+        let data = read_data::<f64>("resources/3.csv").unwrap();
+        let mut source =
+            CsvSource::<f64>::open("resources/3.csv", &ReadOptions::default()).unwrap();
+        let settings = fittable_model::FittingSettings {
+            max_iteration_count: 100,
+            learning_rate: 0.001,
+            eps: 0.00001,
+            starting_theta: array::Array1::zeros(2),
+            batch_size: 4,
+        };
+        let in_memory = GradientDescent::fit::<lms::StochasticKernel>(&data, &settings).unwrap();
+        let streamed =
+            GradientDescent::fit_streaming::<lms::StochasticKernel, _>(&mut source, &settings)
+                .unwrap();
+        assert_eq!(streamed.theta(), in_memory.theta());
+        assert_eq!(
+            streamed.fitting_info().iteration_count,
+            in_memory.fitting_info().iteration_count
+        );
+        assert_eq!(streamed.feature_names(), data.feature_names.as_slice());
+
+        let in_memory = GradientDescent::fit::<lms::MiniBatchKernel>(&data, &settings).unwrap();
+        let streamed =
+            GradientDescent::fit_streaming::<lms::MiniBatchKernel, _>(&mut source, &settings)
+                .unwrap();
+        assert_eq!(streamed.theta(), in_memory.theta());
+
+        let settings = fittable_model::FittingSettings {
+            batch_size: 0,
+            ..settings
+        };
+        assert!(matches!(
+            GradientDescent::fit::<lms::MiniBatchKernel>(&data, &settings),
+            Err(LinearRegressionError::InvalidBatchSize)
+        ));
+
+        // no samples must not look like a fit that kept the starting θ
+        let settings = fittable_model::FittingSettings {
+            batch_size: 4,
+            ..settings
+        };
+        let empty = data.select_rows(&[]);
+        assert!(matches!(
+            GradientDescent::fit::<lms::BatchKernel>(&empty, &settings),
+            Err(LinearRegressionError::EmptyDataset)
+        ));
+        assert!(matches!(
+            GradientDescent::fit_streaming::<lms::MiniBatchKernel, _>(
+                &mut ArraySource::from_training_data(&empty),
+                &settings
+            ),
+            Err(LinearRegressionError::EmptyDataset)
+        ));
+    }
+}
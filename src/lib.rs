@@ -1,3 +1,5 @@
+pub mod cross_validation;
+pub mod data_source;
 mod fittable_model;
 mod gradient_descent;
 mod linear_regression;
@@ -5,18 +7,36 @@ mod lms;
 mod locally_weighted_gradient_descent;
 mod normal_equation;
 mod parametric_algorithm;
+pub mod preprocessing;
+pub mod random;
 mod training_data;
 
-mod array;
+pub mod array;
 
-mod linalg;
+pub mod linalg;
+
+pub mod npy;
 
 mod window;
 
-use fittable_model::FittableModel;
-use linear_regression::{LinearRegressionError, LinearRegressionModel};
-use parametric_algorithm::ParametricAlgorithm;
-use training_data::TrainingDataError;
+// the models and loaders live in private modules, this is their public API
+pub use fittable_model::{FittableModel, FittingInfo, FittingSettings};
+pub use gradient_descent::GradientDescent;
+pub use linear_regression::{LinearRegressionError, LinearRegressionModel};
+pub use lms::{BatchKernel, Kernel, LMSError, MiniBatchKernel, StochasticKernel, StreamingKernel};
+pub use locally_weighted_gradient_descent::{
+    LocallyWeightedLinearRegression, Settings as LocallyWeightedSettings,
+};
+pub use normal_equation::{NormalEquation, Solver};
+pub use parametric_algorithm::{FeatureNames, ParametricAlgorithm};
+pub use training_data::{
+    read_data, read_data_with, read_jsonl, read_jsonl_with, read_libsvm, read_libsvm_with,
+    write_jsonl, write_libsvm, CategoricalColumn, Column, ReadOptions, SplitError, TrainingData,
+    TrainingDataError, INTERCEPT,
+};
+
+use cross_validation::CrossValidationError;
+use preprocessing::{PreprocessingError, Scaler, ScalingMethod};
 
 use std::convert::From;
 
@@ -24,6 +44,9 @@ use std::convert::From;
 pub enum MainError {
     LinearRegressionError(LinearRegressionError),
     TrainingDataError(TrainingDataError),
+    PreprocessingError(PreprocessingError),
+    SplitError(SplitError),
+    CrossValidationError(CrossValidationError),
 }
 
 impl From<LinearRegressionError> for MainError {
@@ -38,51 +61,136 @@ impl From<TrainingDataError> for MainError {
     }
 }
 
+impl From<PreprocessingError> for MainError {
+    fn from(error: PreprocessingError) -> Self {
+        MainError::PreprocessingError(error)
+    }
+}
+
+impl From<SplitError> for MainError {
+    fn from(error: SplitError) -> Self {
+        MainError::SplitError(error)
+    }
+}
+
+impl From<CrossValidationError> for MainError {
+    fn from(error: CrossValidationError) -> Self {
+        MainError::CrossValidationError(error)
+    }
+}
+
 fn print<T>(
     name: &str,
+    feature_names: &[String],
     theta: array::ArrayView1<T>,
     fitting_info: Option<fittable_model::FittingInfo>,
 ) where
     T: num_traits::Float + std::fmt::Debug,
 {
     println!("{}", name);
-    match fitting_info {
-        Some(info) => {
-            println!("{:?}", info);
-        }
-        None => (),
-    };
-    println!("{:?}\n", theta);
+    if let Some(info) = fitting_info {
+        println!("{:?}", info);
+    }
+    for (feature, theta) in feature_names.iter().zip(theta) {
+        println!("{}: {:?}", feature, theta);
+    }
+    println!();
 }
 
 pub fn run() -> Result<(), MainError> {
-    let training_data = training_data::read_data::<f64>("resources/3.csv")?;
+    let data = training_data::read_data::<f64>("resources/3.csv")?;
+    // a fixed seed keeps the printed results the same between runs
+    let (training_data, test_data) = data.train_test_split(0.2, 42)?;
     let fitting_settings = fittable_model::FittingSettings {
         max_iteration_count: 10000,
         learning_rate: 0.001,
         eps: 0.00001,
         starting_theta: array::Array1::<f64>::zeros(training_data.x.ncols()),
+        batch_size: 8,
+    };
+
+    // gradient descent converges in a few steps once x and y are on the same scale
+    let scaler = Scaler::fit_training_data(&training_data, ScalingMethod::Standard, true)?;
+    let mut scaled_data = training_data.clone();
+    scaler.transform_training_data(&mut scaled_data)?;
+    let scaled_settings = fittable_model::FittingSettings {
+        learning_rate: 0.01,
+        eps: 1e-12,
+        starting_theta: fitting_settings.starting_theta.clone(),
+        ..fitting_settings
     };
     let batch_gradient_descent = gradient_descent::GradientDescent::<f64>::fit::<lms::BatchKernel>(
-        &training_data,
-        &fitting_settings,
+        &scaled_data,
+        &scaled_settings,
     )?;
     print(
         "batch gradient descent",
-        batch_gradient_descent.theta(),
+        batch_gradient_descent.feature_names(),
+        scaler
+            .unscale_theta(&batch_gradient_descent.theta())?
+            .view(),
         Some(batch_gradient_descent.fitting_info()),
     );
     let stochastic_gradient_descent = gradient_descent::GradientDescent::<f64>::fit::<
         lms::StochasticKernel,
-    >(&training_data, &fitting_settings)?;
+    >(&scaled_data, &scaled_settings)?;
     print(
         "stochastic gradient descent",
-        stochastic_gradient_descent.theta(),
+        stochastic_gradient_descent.feature_names(),
+        scaler
+            .unscale_theta(&stochastic_gradient_descent.theta())?
+            .view(),
         Some(stochastic_gradient_descent.fitting_info()),
     );
+    let mini_batch_gradient_descent = gradient_descent::GradientDescent::<f64>::fit::<
+        lms::MiniBatchKernel,
+    >(&scaled_data, &scaled_settings)?;
+    print(
+        "mini-batch gradient descent",
+        mini_batch_gradient_descent.feature_names(),
+        scaler
+            .unscale_theta(&mini_batch_gradient_descent.theta())?
+            .view(),
+        Some(mini_batch_gradient_descent.fitting_info()),
+    );
 
     let normal_equation_solver = normal_equation::NormalEquation::<f64>::new(&training_data)?;
-    print("normal equations", normal_equation_solver.theta(), None);
+    print(
+        "normal equations",
+        normal_equation_solver.feature_names(),
+        normal_equation_solver.theta(),
+        None,
+    );
+
+    let qr_normal_equation_solver = normal_equation::NormalEquation::<f64>::with_solver(
+        &training_data,
+        normal_equation::Solver::QR,
+    )?;
+    print(
+        "normal equations (QR)",
+        qr_normal_equation_solver.feature_names(),
+        qr_normal_equation_solver.theta(),
+        None,
+    );
+    println!(
+        "solver: {:?}, rank: {}\n",
+        qr_normal_equation_solver.solver(),
+        qr_normal_equation_solver.rank()
+    );
+    let validation = cross_validation::cross_validate(
+        &training_data,
+        &cross_validation::Settings {
+            folds: cross_validation::Folds::KFold { k: 5 },
+            metric: cross_validation::Metric::RootMeanSquaredError,
+            seed: 42,
+            keep_models: false,
+        },
+        normal_equation::NormalEquation::<f64>::new,
+    )?;
+    println!(
+        "5-fold cross-validation of normal equations\nRMSE: {:?} ± {:?}\n",
+        validation.mean, validation.std
+    );
 
     let locally_weighted_gradient_descent =
         locally_weighted_gradient_descent::LocallyWeightedLinearRegression::<f64>::new(
@@ -93,8 +201,9 @@ pub fn run() -> Result<(), MainError> {
             },
         );
     println!(
-        "locally weighted gradient descent\n{:?}\n",
-        locally_weighted_gradient_descent.predict(&training_data.x.row(0))?,
+        "locally weighted gradient descent\n{:?} (actual {:?})\n",
+        locally_weighted_gradient_descent.predict(&test_data.x.row(0))?,
+        test_data.y[0],
     );
     window::run();
     Ok(())
@@ -1,13 +1,18 @@
 use crate::array;
+use crate::linalg::error::LinalgError;
 
 pub trait Arithmetic<A, S, D>
 where
     S: ndarray::Data<Elem = A>,
     D: ndarray::Dimension,
 {
-    fn sub(&self, rhs: &array::ArrayBase<S, D>) -> array::Array1<A>;
-    fn add(&self, rhs: &array::ArrayBase<S, D>) -> array::Array1<A>;
-    fn scaled_add(&self, alpha: A, rhs: &array::ArrayBase<S, D>) -> array::Array1<A>;
+    fn sub(&self, rhs: &array::ArrayBase<S, D>) -> Result<array::Array1<A>, LinalgError>;
+    fn add(&self, rhs: &array::ArrayBase<S, D>) -> Result<array::Array1<A>, LinalgError>;
+    fn scaled_add(
+        &self,
+        alpha: A,
+        rhs: &array::ArrayBase<S, D>,
+    ) -> Result<array::Array1<A>, LinalgError>;
 }
 
 impl<A, S1, S2> Arithmetic<A, S2, array::Ix1> for array::ArrayBase<S1, array::Ix1>
@@ -16,29 +21,127 @@ where
     S1: ndarray::Data<Elem = A>,
     S2: ndarray::Data<Elem = A>,
 {
-    fn sub(&self, rhs: &array::ArrayBase<S2, array::Ix1>) -> array::Array1<A> {
+    fn sub(&self, rhs: &array::ArrayBase<S2, array::Ix1>) -> Result<array::Array1<A>, LinalgError> {
         self.scaled_add(A::one().neg(), rhs)
     }
-    fn add(&self, rhs: &array::ArrayBase<S2, array::Ix1>) -> array::Array1<A> {
+    fn add(&self, rhs: &array::ArrayBase<S2, array::Ix1>) -> Result<array::Array1<A>, LinalgError> {
         self.scaled_add(A::one(), rhs)
     }
-    fn scaled_add(&self, alpha: A, rhs: &array::ArrayBase<S2, array::Ix1>) -> array::Array1<A> {
+    fn scaled_add(
+        &self,
+        alpha: A,
+        rhs: &array::ArrayBase<S2, array::Ix1>,
+    ) -> Result<array::Array1<A>, LinalgError> {
         scaled_add(alpha, self, rhs)
     }
 }
 
+// named *_checked so that they do not clash with std::ops::AddAssign and
+// SubAssign, which panic on a shape mismatch
+pub trait ArithmeticAssign<A, S, D>
+where
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+{
+    // self ← self + rhs
+    fn add_assign_checked(&mut self, rhs: &array::ArrayBase<S, D>) -> Result<(), LinalgError>;
+    // self ← self - rhs
+    fn sub_assign_checked(&mut self, rhs: &array::ArrayBase<S, D>) -> Result<(), LinalgError>;
+    // self ← self + α·rhs, without allocating
+    fn scaled_add_assign(
+        &mut self,
+        alpha: A,
+        rhs: &array::ArrayBase<S, D>,
+    ) -> Result<(), LinalgError>;
+}
+
+impl<A, S1, S2> ArithmeticAssign<A, S2, array::Ix1> for array::ArrayBase<S1, array::Ix1>
+where
+    A: num_traits::Float,
+    S1: ndarray::DataMut<Elem = A>,
+    S2: ndarray::Data<Elem = A>,
+{
+    fn add_assign_checked(
+        &mut self,
+        rhs: &array::ArrayBase<S2, array::Ix1>,
+    ) -> Result<(), LinalgError> {
+        self.scaled_add_assign(A::one(), rhs)
+    }
+    fn sub_assign_checked(
+        &mut self,
+        rhs: &array::ArrayBase<S2, array::Ix1>,
+    ) -> Result<(), LinalgError> {
+        self.scaled_add_assign(A::one().neg(), rhs)
+    }
+    fn scaled_add_assign(
+        &mut self,
+        alpha: A,
+        rhs: &array::ArrayBase<S2, array::Ix1>,
+    ) -> Result<(), LinalgError> {
+        if self.len() != rhs.len() {
+            return Err(LinalgError::shape_mismatch(self.shape(), rhs.shape()));
+        }
+        for (a, b) in self.into_iter().zip(rhs) {
+            *a = *a + alpha * *b;
+        }
+        Ok(())
+    }
+}
+
 fn scaled_add<A, S1, S2>(
     alpha: A,
     lhs: &array::ArrayBase<S1, array::Ix1>,
     rhs: &array::ArrayBase<S2, array::Ix1>,
-) -> array::ArrayBase<ndarray::OwnedRepr<A>, array::Ix1>
+) -> Result<array::ArrayBase<ndarray::OwnedRepr<A>, array::Ix1>, LinalgError>
 where
     S1: ndarray::Data<Elem = A>,
     S2: ndarray::Data<Elem = A>,
     A: num_traits::Float + std::iter::Sum,
 {
-    lhs.into_iter()
-        .zip(rhs.into_iter())
+    if lhs.len() != rhs.len() {
+        return Err(LinalgError::shape_mismatch(lhs.shape(), rhs.shape()));
+    }
+    Ok(lhs
+        .into_iter()
+        .zip(rhs)
         .map(|(a, b)| *a + (alpha * *b))
-        .collect()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arithmetic, ArithmeticAssign};
+    use crate::array;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = array![1.0, 2.0, 3.0];
+        let b = array![4.0, 5.0, 6.0];
+        assert_eq!(a.add(&b).unwrap(), array![5.0, 7.0, 9.0]);
+        assert_eq!(a.sub(&b).unwrap(), array![-3.0, -3.0, -3.0]);
+        assert_eq!(a.scaled_add(2.0, &b).unwrap(), array![9.0, 12.0, 15.0]);
+        assert!(a.add(&array![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_assign() {
+        let mut a = array![1.0, 2.0, 3.0];
+        let b = array![4.0, 5.0, 6.0];
+        a.scaled_add_assign(2.0, &b).unwrap();
+        assert_eq!(a, array![9.0, 12.0, 15.0]);
+        a.scaled_add_assign(-1.0, &b).unwrap();
+        assert_eq!(a, array![5.0, 7.0, 9.0]);
+        a.sub_assign_checked(&b).unwrap();
+        assert_eq!(a, array![1.0, 2.0, 3.0]);
+        a.add_assign_checked(&b).unwrap();
+        assert_eq!(a, array![5.0, 7.0, 9.0]);
+        assert!(a.add_assign_checked(&array![1.0]).is_err());
+
+        let mut m = array![[1.0, 2.0], [3.0, 4.0]];
+        assert!(m.row_mut(1).scaled_add_assign(1.0, &b).is_err());
+        m.row_mut(1)
+            .scaled_add_assign(1.0, &array![1.0, 1.0])
+            .unwrap();
+        assert_eq!(m, array![[1.0, 2.0], [4.0, 5.0]]);
+    }
 }
@@ -1,28 +1,95 @@
-use std::alloc::{alloc, dealloc, Layout};
-use std::ptr::write;
+use std::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::ptr::{self, write, NonNull};
 
+use crate::array;
+use crate::linalg::error::LinalgError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LayoutType {
     RowMajor,
     ColumnMajor,
 }
 
-pub trait MemoryLayout {
-    type NValues;
+pub trait Order {
+    const LAYOUT: LayoutType;
+    // order of the same memory read with the axes reversed
+    type Transposed: Order;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RowMajor;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnMajor;
+
+impl Order for RowMajor {
+    const LAYOUT: LayoutType = LayoutType::RowMajor;
+    type Transposed = ColumnMajor;
+}
+
+impl Order for ColumnMajor {
+    const LAYOUT: LayoutType = LayoutType::ColumnMajor;
+    type Transposed = RowMajor;
+}
+
+pub trait MemoryLayout: Clone {
+    type NValues: AsRef<[usize]> + Clone;
     fn new(dimensions: &Self::NValues) -> Self;
+    fn compute_strides(dimensions: &Self::NValues) -> Self::NValues;
     fn strides(&self) -> &Self::NValues;
     fn dimensions(&self) -> &Self::NValues;
+
+    fn len(&self) -> usize {
+        self.dimensions().as_ref().iter().product()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Position of the element in storage, None when the index is out of bounds
+    fn offset(&self, index: &Self::NValues) -> Option<usize> {
+        let mut offset = 0;
+        for ((i, dimension), stride) in index
+            .as_ref()
+            .iter()
+            .zip(self.dimensions().as_ref())
+            .zip(self.strides().as_ref())
+        {
+            if i >= dimension {
+                return None;
+            }
+            offset += i * stride;
+        }
+        Some(offset)
+    }
 }
 
-pub struct NDArrayStorage<const L: LayoutType, const N: usize> {
+#[derive(Debug)]
+pub struct NDArrayStorage<O: Order, const N: usize> {
     dimensions: [usize; N],
     strides: [usize; N],
+    order: PhantomData<O>,
+}
+
+// derived Clone would require O: Clone
+impl<O: Order, const N: usize> Clone for NDArrayStorage<O, N> {
+    fn clone(&self) -> Self {
+        Self {
+            dimensions: self.dimensions,
+            strides: self.strides,
+            order: PhantomData,
+        }
+    }
 }
 
 #[inline]
-fn calculate_strides<const L: LayoutType, const N: usize>(dimensions: &[usize; N]) {
+fn calculate_strides<O: Order, const N: usize>(dimensions: &[usize; N]) -> [usize; N] {
     let mut stride = 1;
     let mut strides = [0; N];
-    match L {
+    match O::LAYOUT {
         LayoutType::RowMajor => {
             for i in (0..N).rev() {
                 strides[i] = stride;
@@ -39,22 +106,34 @@ fn calculate_strides<const L: LayoutType, const N: usize>(dimensions: &[usize; N
     strides
 }
 
-impl<const L: LayoutType, const N: usize> NDArrayStorage<L, N> {
+impl<O: Order, const N: usize> NDArrayStorage<O, N> {
     pub fn update_dimensions(&mut self, dimensions: &[usize; N]) {
-        self.dimensions = dimensions;
-        self.strides = calculate_strides(dimensions);
+        self.dimensions = *dimensions;
+        self.strides = calculate_strides::<O, N>(dimensions);
+    }
+
+    // The same memory seen with reversed axes, which is how transposition works without copying
+    pub fn transposed(&self) -> NDArrayStorage<O::Transposed, N> {
+        let mut dimensions = self.dimensions;
+        dimensions.reverse();
+        NDArrayStorage::<O::Transposed, N>::new(&dimensions)
     }
 }
 
-impl<const L: LayoutType, const N: usize> MemoryLayout for NDArrayStorage<L, N> {
+impl<O: Order, const N: usize> MemoryLayout for NDArrayStorage<O, N> {
     type NValues = [usize; N];
     fn new(dimensions: &Self::NValues) -> Self {
-        NDArrayStorage::<L, N>{
-            dimensions,
-            strides: calculate_strides(dimensions),
+        NDArrayStorage::<O, N> {
+            dimensions: *dimensions,
+            strides: Self::compute_strides(dimensions),
+            order: PhantomData,
         }
     }
 
+    fn compute_strides(dimensions: &Self::NValues) -> Self::NValues {
+        calculate_strides::<O, N>(dimensions)
+    }
+
     fn strides(&self) -> &Self::NValues {
         &self.strides
     }
@@ -64,10 +143,27 @@ impl<const L: LayoutType, const N: usize> MemoryLayout for NDArrayStorage<L, N>
     }
 }
 
-pub struct NDArray<A, L: MemoryLayout>
-{
-    data: *mut A,
+pub type RowMajorStorage<const N: usize> = NDArrayStorage<RowMajor, N>;
+pub type ColumnMajorStorage<const N: usize> = NDArrayStorage<ColumnMajor, N>;
+
+// Owned N-dimensional array in a single allocation laid out by L
+pub struct NDArray<A, L: MemoryLayout> {
+    data: NonNull<A>,
     layout: L,
+    // the array owns its elements, which matters for drop checking
+    elements: PhantomData<A>,
+}
+
+unsafe impl<A: Send, L: MemoryLayout + Send> Send for NDArray<A, L> {}
+unsafe impl<A: Sync, L: MemoryLayout + Sync> Sync for NDArray<A, L> {}
+
+fn allocate<A>(len: usize) -> NonNull<A> {
+    let layout = Layout::array::<A>(len).expect("array size overflows isize");
+    if layout.size() == 0 {
+        return NonNull::dangling();
+    }
+    let data = unsafe { alloc(layout) as *mut A };
+    NonNull::new(data).unwrap_or_else(|| handle_alloc_error(layout))
 }
 
 impl<A, L> NDArray<A, L>
@@ -78,27 +174,454 @@ where
     where
         A: Clone,
     {
-        let total_size = dim.iter().product();
+        let layout = L::new(&dim);
+        let total_size = layout.len();
+        let data = allocate::<A>(total_size);
+        for i in 0..total_size {
+            unsafe {
+                write(data.as_ptr().add(i), initial.clone());
+            }
+        }
+        NDArray {
+            data,
+            layout,
+            elements: PhantomData,
+        }
+    }
 
-        let layout = Layout::array::<A>(total_size).unwrap();
-        let data = unsafe { alloc(layout) as *mut A };
+    // Takes ownership of values already in storage order
+    pub fn from_vec(dim: L::NValues, values: Vec<A>) -> Result<Self, LinalgError> {
+        let layout = L::new(&dim);
+        if values.len() != layout.len() {
+            return Err(LinalgError::shape_mismatch(dim.as_ref(), &[values.len()]));
+        }
+        Ok(Self::from_iter_unchecked(layout, values))
+    }
 
-        for i in 0..total_size {
+    // `values` must yield exactly layout.len() elements
+    fn from_iter_unchecked<I>(layout: L, values: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+    {
+        let total_size = layout.len();
+        let data = allocate::<A>(total_size);
+        let mut written = 0;
+        for value in values.into_iter().take(total_size) {
             unsafe {
-                write(data.add(i), initial.clone());
+                write(data.as_ptr().add(written), value);
             }
+            written += 1;
         }
+        assert_eq!(written, total_size);
+        NDArray {
+            data,
+            layout,
+            elements: PhantomData,
+        }
+    }
 
-        let strides = L::compute_strides(&dim);
-        let layout
-        NDArray { data, dim, strides }
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    pub fn dimensions(&self) -> &L::NValues {
+        self.layout.dimensions()
+    }
+
+    pub fn len(&self) -> usize {
+        self.layout.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layout.is_empty()
+    }
+
+    pub fn get(&self, index: &L::NValues) -> Option<&A> {
+        self.layout
+            .offset(index)
+            .map(|offset| &self.as_slice()[offset])
+    }
+
+    pub fn get_mut(&mut self, index: &L::NValues) -> Option<&mut A> {
+        self.layout
+            .offset(index)
+            .map(|offset| &mut self.as_mut_slice()[offset])
+    }
+
+    // Elements in storage order
+    pub fn as_slice(&self) -> &[A] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr(), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [A] {
+        unsafe { std::slice::from_raw_parts_mut(self.data.as_ptr(), self.len()) }
+    }
+
+    pub fn view(&self) -> NDArrayView<'_, A, L> {
+        NDArrayView {
+            data: self.as_slice(),
+            layout: self.layout.clone(),
+        }
+    }
+
+    pub fn view_mut(&mut self) -> NDArrayViewMut<'_, A, L> {
+        let layout = self.layout.clone();
+        NDArrayViewMut {
+            data: self.as_mut_slice(),
+            layout,
+        }
+    }
+}
+
+impl<A, O, const N: usize> NDArray<A, NDArrayStorage<O, N>>
+where
+    O: Order,
+{
+    pub fn from_array<S, D>(a: &array::ArrayBase<S, D>) -> Result<Self, LinalgError>
+    where
+        A: Clone,
+        S: ndarray::Data<Elem = A>,
+        D: ndarray::Dimension,
+    {
+        let dimensions: [usize; N] = a
+            .shape()
+            .try_into()
+            .map_err(|_| LinalgError::shape_mismatch(&[0; N], a.shape()))?;
+        let layout = NDArrayStorage::<O, N>::new(&dimensions);
+        let view = a.inner_impl().view();
+        // logical iteration of the reversed axes visits the elements in column-major order
+        let result = match O::LAYOUT {
+            LayoutType::RowMajor => Self::from_iter_unchecked(layout, view.into_iter().cloned()),
+            LayoutType::ColumnMajor => {
+                Self::from_iter_unchecked(layout, view.reversed_axes().into_iter().cloned())
+            }
+        };
+        Ok(result)
+    }
+
+    pub fn to_array<D>(&self) -> Result<array::Array<A, D>, LinalgError>
+    where
+        A: Clone,
+        D: ndarray::Dimension,
+    {
+        self.view().to_array()
+    }
+
+    pub fn t(&self) -> NDArrayView<'_, A, NDArrayStorage<O::Transposed, N>> {
+        self.view().t()
     }
 }
 
-impl<A, const N: usize> Drop for NDArray<A, N> {
+impl<A, L> Drop for NDArray<A, L>
+where
+    L: MemoryLayout,
+{
     fn drop(&mut self) {
-        let total_size = self.dim.iter().product();
-        let layout = Layout::array::<A>(total_size).unwrap();
-        unsafe { dealloc(self.data as *mut u8, layout) };
+        let total_size = self.len();
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.data.as_ptr(),
+                total_size,
+            ));
+        }
+        let layout = Layout::array::<A>(total_size).expect("array size overflows isize");
+        if layout.size() != 0 {
+            unsafe { dealloc(self.data.as_ptr() as *mut u8, layout) };
+        }
+    }
+}
+
+impl<A, L> Clone for NDArray<A, L>
+where
+    A: Clone,
+    L: MemoryLayout,
+{
+    fn clone(&self) -> Self {
+        Self::from_iter_unchecked(self.layout.clone(), self.as_slice().iter().cloned())
+    }
+}
+
+impl<A, L> Index<L::NValues> for NDArray<A, L>
+where
+    L: MemoryLayout,
+{
+    type Output = A;
+
+    fn index(&self, index: L::NValues) -> &A {
+        match self.layout.offset(&index) {
+            Some(offset) => &self.as_slice()[offset],
+            None => out_of_bounds(index.as_ref(), self.dimensions().as_ref()),
+        }
+    }
+}
+
+impl<A, L> IndexMut<L::NValues> for NDArray<A, L>
+where
+    L: MemoryLayout,
+{
+    fn index_mut(&mut self, index: L::NValues) -> &mut A {
+        match self.layout.offset(&index) {
+            Some(offset) => &mut self.as_mut_slice()[offset],
+            None => out_of_bounds(index.as_ref(), self.layout.dimensions().as_ref()),
+        }
+    }
+}
+
+impl<A: std::fmt::Debug, L: MemoryLayout> std::fmt::Debug for NDArray<A, L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.view().fmt(f)
+    }
+}
+
+fn out_of_bounds(index: &[usize], dimensions: &[usize]) -> ! {
+    panic!(
+        "index {:?} is out of bounds for array of dimensions {:?}",
+        index, dimensions
+    )
+}
+
+pub struct NDArrayView<'a, A, L: MemoryLayout> {
+    data: &'a [A],
+    layout: L,
+}
+
+impl<'a, A, L> NDArrayView<'a, A, L>
+where
+    L: MemoryLayout,
+{
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    pub fn dimensions(&self) -> &L::NValues {
+        self.layout.dimensions()
+    }
+
+    pub fn len(&self) -> usize {
+        self.layout.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layout.is_empty()
+    }
+
+    pub fn get(&self, index: &L::NValues) -> Option<&'a A> {
+        self.layout.offset(index).map(|offset| &self.data[offset])
+    }
+
+    pub fn as_slice(&self) -> &'a [A] {
+        self.data
+    }
+
+    pub fn to_owned(&self) -> NDArray<A, L>
+    where
+        A: Clone,
+    {
+        NDArray::from_iter_unchecked(self.layout.clone(), self.data.iter().cloned())
+    }
+}
+
+impl<'a, A, O, const N: usize> NDArrayView<'a, A, NDArrayStorage<O, N>>
+where
+    O: Order,
+{
+    pub fn t(&self) -> NDArrayView<'a, A, NDArrayStorage<O::Transposed, N>> {
+        NDArrayView {
+            data: self.data,
+            layout: self.layout.transposed(),
+        }
+    }
+
+    pub fn to_array<D>(&self) -> Result<array::Array<A, D>, LinalgError>
+    where
+        A: Clone,
+        D: ndarray::Dimension,
+    {
+        let dimensions = self.layout.dimensions();
+        let shape = ndarray::ShapeBuilder::set_f(
+            ndarray::IxDyn(dimensions),
+            O::LAYOUT == LayoutType::ColumnMajor,
+        );
+        ndarray::Array::from_shape_vec(shape, self.data.to_vec())
+            .and_then(|a| a.into_dimensionality::<D>())
+            .map(array::ArrayBase::new)
+            .map_err(|_| LinalgError::shape_mismatch(dimensions, &[D::NDIM.unwrap_or(0)]))
+    }
+}
+
+impl<A, L> Index<L::NValues> for NDArrayView<'_, A, L>
+where
+    L: MemoryLayout,
+{
+    type Output = A;
+
+    fn index(&self, index: L::NValues) -> &A {
+        match self.layout.offset(&index) {
+            Some(offset) => &self.data[offset],
+            None => out_of_bounds(index.as_ref(), self.dimensions().as_ref()),
+        }
+    }
+}
+
+impl<A: std::fmt::Debug, L: MemoryLayout> std::fmt::Debug for NDArrayView<'_, A, L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NDArrayView")
+            .field("dimensions", &self.layout.dimensions().as_ref())
+            .field("strides", &self.layout.strides().as_ref())
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+pub struct NDArrayViewMut<'a, A, L: MemoryLayout> {
+    data: &'a mut [A],
+    layout: L,
+}
+
+impl<A, L> NDArrayViewMut<'_, A, L>
+where
+    L: MemoryLayout,
+{
+    pub fn dimensions(&self) -> &L::NValues {
+        self.layout.dimensions()
+    }
+
+    pub fn get_mut(&mut self, index: &L::NValues) -> Option<&mut A> {
+        self.layout
+            .offset(index)
+            .map(|offset| &mut self.data[offset])
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [A] {
+        self.data
+    }
+
+    pub fn fill(&mut self, value: A)
+    where
+        A: Clone,
+    {
+        self.data.fill(value)
+    }
+}
+
+impl<A, L> Index<L::NValues> for NDArrayViewMut<'_, A, L>
+where
+    L: MemoryLayout,
+{
+    type Output = A;
+
+    fn index(&self, index: L::NValues) -> &A {
+        match self.layout.offset(&index) {
+            Some(offset) => &self.data[offset],
+            None => out_of_bounds(index.as_ref(), self.dimensions().as_ref()),
+        }
+    }
+}
+
+impl<A, L> IndexMut<L::NValues> for NDArrayViewMut<'_, A, L>
+where
+    L: MemoryLayout,
+{
+    fn index_mut(&mut self, index: L::NValues) -> &mut A {
+        match self.layout.offset(&index) {
+            Some(offset) => &mut self.data[offset],
+            None => out_of_bounds(index.as_ref(), self.layout.dimensions().as_ref()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::array::Transpose;
+    use crate::linalg::Dot;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_strides() {
+        let row_major = RowMajorStorage::<3>::new(&[2, 3, 4]);
+        assert_eq!(row_major.strides(), &[12, 4, 1]);
+        let column_major = ColumnMajorStorage::<3>::new(&[2, 3, 4]);
+        assert_eq!(column_major.strides(), &[1, 2, 6]);
+        assert_eq!(column_major.offset(&[1, 2, 3]), Some(1 + 4 + 18));
+        assert_eq!(column_major.offset(&[2, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut a = NDArray::<f64, RowMajorStorage<2>>::new([2, 3], 0.0);
+        a[[1, 2]] = 5.0;
+        assert_eq!(a.as_slice(), &[0.0, 0.0, 0.0, 0.0, 0.0, 5.0]);
+        assert_eq!(a.get(&[1, 2]), Some(&5.0));
+        assert_eq!(a.get(&[2, 0]), None);
+        *a.get_mut(&[0, 1]).unwrap() = 1.0;
+        a.view_mut()[[0, 0]] = 2.0;
+        assert_eq!(a.view()[[0, 1]], 1.0);
+        assert_eq!(a.t()[[1, 0]], 1.0);
+        assert_eq!(a.t()[[0, 0]], 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let a = NDArray::<f64, ColumnMajorStorage<2>>::new([2, 3], 0.0);
+        let _ = a[[0, 3]];
+    }
+
+    #[test]
+    fn test_clone_and_drop() {
+        let counter = Rc::new(());
+        let a = NDArray::<Rc<()>, RowMajorStorage<2>>::new([3, 4], counter.clone());
+        assert_eq!(Rc::strong_count(&counter), 13);
+        let b = a.clone();
+        assert_eq!(Rc::strong_count(&counter), 25);
+        drop(a);
+        assert_eq!(Rc::strong_count(&counter), 13);
+        drop(b);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let empty = NDArray::<Rc<()>, RowMajorStorage<2>>::new([0, 4], counter.clone());
+        assert!(empty.is_empty());
+        drop(empty.clone());
+    }
+
+    #[test]
+    fn test_array_conversion() {
+        let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let row_major = NDArray::<f64, RowMajorStorage<2>>::from_array(&a).unwrap();
+        assert_eq!(row_major.as_slice(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let column_major = NDArray::<f64, ColumnMajorStorage<2>>::from_array(&a).unwrap();
+        assert_eq!(column_major.as_slice(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(column_major[[0, 2]], 3.0);
+
+        assert_eq!(row_major.to_array::<array::Ix2>().unwrap(), a);
+        assert_eq!(column_major.to_array::<array::Ix2>().unwrap(), a);
+        assert!(column_major.to_array::<array::Ix1>().is_err());
+        assert!(NDArray::<f64, RowMajorStorage<1>>::from_array(&a).is_err());
+    }
+
+    #[test]
+    fn test_dot_and_llt_backend() {
+        let a = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let b = array![[1.0, 0.5, 2.0], [-1.0, 3.0, 0.0]];
+        let expected = a.dot(&b).unwrap();
+        let a = NDArray::<f64, RowMajorStorage<2>>::from_array(&a).unwrap();
+        let b = NDArray::<f64, ColumnMajorStorage<2>>::from_array(&b).unwrap();
+        let result = a.dot(&b).unwrap();
+        assert_eq!(result.to_array::<array::Ix2>().unwrap(), expected);
+
+        let v = NDArray::<f64, RowMajorStorage<1>>::from_vec([2], vec![1.0, -1.0]).unwrap();
+        let result = a.view().dot(&v.view()).unwrap();
+        assert_eq!(result.as_slice(), &[-1.0, -1.0, -1.0]);
+
+        // AᵀA through a transposed view
+        let gram = a.t().dot(&a.view()).unwrap();
+        let l = gram.view().llt().unwrap();
+        let expected = array![[35.0, 44.0], [44.0, 56.0]];
+        let reconstructed = l.to_2d_array().dot(&l.to_2d_array().t()).unwrap();
+        for (x, y) in reconstructed.into_iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-10);
+        }
     }
 }
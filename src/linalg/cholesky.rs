@@ -1,4 +1,6 @@
 use crate::array;
+use crate::linalg::array_base::{NDArray, NDArrayStorage, NDArrayView, Order};
+use crate::linalg::error::LinalgError;
 
 pub struct LMatrix<A> {
     data: array::Array1<A>,
@@ -9,32 +11,63 @@ impl<A> LMatrix<A>
 where
     A: num_traits::Float,
 {
-    pub fn inv(&self) -> array::ArrayBase<ndarray::OwnedRepr<A>, array::Ix2> {
-        let mut result = array::Array2::<A>::zeros((self.n, self.n));
-        for j in 0..self.n {
-            // Forward substitution
-            let mut y = array::Array1::<A>::zeros(self.n);
-            for i in 0..self.n {
-                let mut sum = A::zero();
-                for k in 0..i {
-                    sum = sum + self.l(i, k) * y[k];
-                }
-                let e_i = if i == j { A::one() } else { A::zero() };
-                y[i] = (e_i - sum) / self.l(i, i);
+    // Solves LLᵀx = b by forward substitution Ly = b followed by backward substitution Lᵀx = y
+    pub fn solve<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix1>,
+    ) -> Result<array::Array1<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        if b.len() != self.n {
+            return Err(LinalgError::shape_mismatch(&[self.n, self.n], b.shape()));
+        }
+        let mut y = array::Array1::<A>::zeros(self.n);
+        for i in 0..self.n {
+            let mut sum = A::zero();
+            for k in 0..i {
+                sum = sum + self.l(i, k) * y[k];
             }
+            y[i] = (b[i] - sum) / self.l(i, i);
+        }
 
-            // Backward substitution
-            let mut x = array::Array1::<A>::zeros(self.n);
-            for i in (0..self.n).rev() {
-                let mut sum = A::zero();
-                for k in i + 1..self.n {
-                    sum = sum + self.lt(i, k) * x[k];
-                }
-                x[i] = (y[i] - sum) / self.lt(i, i);
+        let mut x = array::Array1::<A>::zeros(self.n);
+        for i in (0..self.n).rev() {
+            let mut sum = A::zero();
+            for k in i + 1..self.n {
+                sum = sum + self.lt(i, k) * x[k];
             }
-            result.column_mut(j).assign(&x.view());
+            x[i] = (y[i] - sum) / self.lt(i, i);
         }
-        result
+        Ok(x)
+    }
+
+    // Solves LLᵀX = B column by column
+    pub fn solve_many<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix2>,
+    ) -> Result<array::ArrayBase<ndarray::OwnedRepr<A>, array::Ix2>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        if b.nrows() != self.n {
+            return Err(LinalgError::shape_mismatch(&[self.n, self.n], b.shape()));
+        }
+        let mut result = array::Array2::<A>::zeros((self.n, b.ncols()));
+        for j in 0..b.ncols() {
+            let b_j: array::Array1<A> = (0..self.n).map(|i| b[(i, j)]).collect();
+            result.column_mut(j).assign(&self.solve(&b_j)?);
+        }
+        Ok(result)
+    }
+
+    #[allow(dead_code)]
+    pub fn inv(&self) -> Result<array::Array2<A>, LinalgError> {
+        let mut identity = array::Array2::<A>::zeros((self.n, self.n));
+        for i in 0..self.n {
+            identity[(i, i)] = A::one();
+        }
+        self.solve_many(&identity)
     }
 
     #[allow(dead_code)]
@@ -69,11 +102,12 @@ where
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub trait LLT<S, A>
 where
     S: ndarray::Data<Elem = A>,
 {
-    fn llt(&self) -> LMatrix<A>;
+    fn llt(&self) -> Result<LMatrix<A>, LinalgError>;
 }
 
 impl<S, A> LLT<S, A> for array::ArrayBase<S, array::Ix2>
@@ -81,31 +115,72 @@ where
     S: ndarray::Data<Elem = A>,
     A: num_traits::Float,
 {
-    fn llt(&self) -> LMatrix<A> {
-        assert!(self.nrows() == self.ncols());
-        let n = self.nrows();
-        let mut result = array::Array1::<A>::zeros(n * (n + 1) / 2);
-        fn l_index(i: usize, j: usize) -> usize {
-            (i * (i + 1) / 2) + j
-        }
-        for i in 0..n {
-            for j in 0..i + 1 {
-                let mut sum = A::zero();
-                if i == j {
-                    for k in 0..i {
-                        sum = sum + result[l_index(i, k)].powi(2);
-                    }
-                    result[l_index(i, i)] = (self[(i, i)] - sum).sqrt();
-                } else {
-                    for k in 0..j {
-                        sum = sum + result[l_index(i, k)] * result[l_index(j, k)];
-                    }
-                    result[l_index(i, j)] = (self[(i, j)] - sum) / result[l_index(j, j)];
+    fn llt(&self) -> Result<LMatrix<A>, LinalgError> {
+        cholesky(self.nrows(), self.ncols(), |i, j| self[(i, j)])
+    }
+}
+
+// NDArray has no ndarray storage type to fill in S of LLT, so it gets
+// inherent methods instead
+impl<A, O> NDArrayView<'_, A, NDArrayStorage<O, 2>>
+where
+    A: num_traits::Float,
+    O: Order,
+{
+    pub fn llt(&self) -> Result<LMatrix<A>, LinalgError> {
+        let [rows, cols] = *self.dimensions();
+        cholesky(rows, cols, |i, j| self[[i, j]])
+    }
+}
+
+impl<A, O> NDArray<A, NDArrayStorage<O, 2>>
+where
+    A: num_traits::Float,
+    O: Order,
+{
+    pub fn llt(&self) -> Result<LMatrix<A>, LinalgError> {
+        self.view().llt()
+    }
+}
+
+fn cholesky<A, F>(rows: usize, cols: usize, a: F) -> Result<LMatrix<A>, LinalgError>
+where
+    A: num_traits::Float,
+    F: Fn(usize, usize) -> A,
+{
+    if rows != cols {
+        return Err(LinalgError::NotSquare { rows, cols });
+    }
+    let n = rows;
+    let mut result = array::Array1::<A>::zeros(n * (n + 1) / 2);
+    fn l_index(i: usize, j: usize) -> usize {
+        (i * (i + 1) / 2) + j
+    }
+    // a pivot this small compared to the diagonal is rounding noise of a
+    // singular matrix rather than a positive value
+    let max_diagonal = (0..n).fold(A::zero(), |max, i| max.max(a(i, i)));
+    let tolerance = max_diagonal * A::epsilon() * A::from(n).unwrap_or(A::one());
+    for i in 0..n {
+        for j in 0..i + 1 {
+            let mut sum = A::zero();
+            if i == j {
+                for k in 0..i {
+                    sum = sum + result[l_index(i, k)].powi(2);
+                }
+                let pivot = a(i, i) - sum;
+                if pivot <= tolerance || pivot.is_nan() {
+                    return Err(LinalgError::NotPositiveDefinite { pivot: i });
+                }
+                result[l_index(i, i)] = pivot.sqrt();
+            } else {
+                for k in 0..j {
+                    sum = sum + result[l_index(i, k)] * result[l_index(j, k)];
                 }
+                result[l_index(i, j)] = (a(i, j) - sum) / result[l_index(j, j)];
             }
         }
-        LMatrix { data: result, n }
     }
+    Ok(LMatrix { data: result, n })
 }
 
 #[cfg(test)]
@@ -114,6 +189,7 @@ mod tests {
     use crate::array;
     use crate::array::Transpose;
     use crate::linalg::Dot;
+    use crate::linalg::LinalgError;
 
     #[test]
     fn test_llt_decomposition() {
@@ -122,9 +198,69 @@ mod tests {
             [12.0, 37.0, -43.0],
             [-16.0, -43.0, 98.0]
         ];
-        let l_matrix = a.llt();
+        let l_matrix = a.llt().unwrap();
         let l_2d = l_matrix.to_2d_array();
-        let reconstructed_a = l_2d.dot(&l_2d.t());
+        let reconstructed_a = l_2d.dot(&l_2d.t()).unwrap();
         assert_eq!(a, reconstructed_a);
     }
+
+    #[test]
+    fn test_llt_solve() {
+        let a = array![
+            [4.0, 12.0, -16.0],
+            [12.0, 37.0, -43.0],
+            [-16.0, -43.0, 98.0]
+        ];
+        let x = array![1.0_f64, -2.0, 3.0];
+        let b = array![-68.0, -191.0, 364.0];
+        let result = a.llt().unwrap().solve(&b).unwrap();
+        for i in 0..3 {
+            assert!((result[i] - x[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_llt_solve_many() {
+        let a = array![[4.0, 2.0], [2.0, 3.0]];
+        let x = array![[1.0_f64, 0.5], [-1.0, 2.0]];
+        let b = a.dot(&x).unwrap();
+        let result = a.llt().unwrap().solve_many(&b).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((result[(i, j)] - x[(i, j)]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_llt_inv() {
+        let a = array![[4.0_f64, 2.0], [2.0, 3.0]];
+        let inverse = a.llt().unwrap().inv().unwrap();
+        let identity = a.dot(&inverse).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((identity[(i, j)] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_llt_errors() {
+        let not_square = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        assert_eq!(
+            not_square.llt().err(),
+            Some(LinalgError::NotSquare { rows: 2, cols: 3 })
+        );
+
+        // second column is twice the first, so the second pivot is zero
+        let singular = array![[1.0, 2.0], [2.0, 4.0]];
+        assert_eq!(
+            singular.llt().err(),
+            Some(LinalgError::NotPositiveDefinite { pivot: 1 })
+        );
+
+        let l_matrix = array![[4.0, 2.0], [2.0, 3.0]].llt().unwrap();
+        assert!(l_matrix.solve(&array![1.0, 2.0, 3.0]).is_err());
+    }
 }
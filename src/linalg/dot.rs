@@ -1,8 +1,12 @@
 use crate::array;
+use crate::linalg::array_base::{
+    MemoryLayout, NDArray, NDArrayStorage, NDArrayView, Order, RowMajorStorage,
+};
+use crate::linalg::error::LinalgError;
 
 pub trait Dot<Rhs> {
     type Output;
-    fn dot(&self, rhs: &Rhs) -> Self::Output;
+    fn dot(&self, rhs: &Rhs) -> Result<Self::Output, LinalgError>;
 }
 
 type Ix1 = array::Ix1;
@@ -16,36 +20,286 @@ where
 {
     type Output = A;
 
-    fn dot(&self, rhs: &array::ArrayBase<S2, Ix1>) -> A {
+    fn dot(&self, rhs: &array::ArrayBase<S2, Ix1>) -> Result<A, LinalgError> {
+        if self.len() != rhs.len() {
+            return Err(LinalgError::shape_mismatch(self.shape(), rhs.shape()));
+        }
         let mut result = A::zero();
         for i in 0..self.len() {
             result = result + (self[i] * rhs[i]);
         }
-        result
+        Ok(result)
     }
 }
 
-impl<A, S, S2> Dot<array::ArrayBase<S2, Ix2>> for array::ArrayBase<S, Ix2>
+impl<A, S, S2> Dot<array::ArrayBase<S2, Ix1>> for array::ArrayBase<S, Ix2>
 where
     S: ndarray::Data<Elem = A>,
     S2: ndarray::Data<Elem = A>,
-    A: num_traits::Float + num_traits::Zero,
+    A: num_traits::Float,
 {
-    type Output = array::Array2<A>;
+    type Output = array::Array1<A>;
 
-    fn dot(&self, rhs: &array::ArrayBase<S2, Ix2>) -> array::Array2<A> {
-        assert!(self.ncols() == rhs.nrows());
-        let mut result = array::Array2::<A>::zeros((self.nrows(), rhs.ncols()));
-        for i in 0..self.nrows() {
-            for j in 0..rhs.ncols() {
+    fn dot(&self, rhs: &array::ArrayBase<S2, Ix1>) -> Result<array::Array1<A>, LinalgError> {
+        if self.ncols() != rhs.len() {
+            return Err(LinalgError::shape_mismatch(self.shape(), rhs.shape()));
+        }
+        Ok((0..self.nrows())
+            .map(|i| {
                 let mut sum = A::zero();
                 for k in 0..self.ncols() {
-                    sum = sum + self[(i, k)] * rhs[(k, j)];
+                    sum = sum + self[(i, k)] * rhs[k];
                 }
-                result[(i, j)] = sum;
+                sum
+            })
+            .collect())
+    }
+}
+
+impl<A, S, S2> Dot<array::ArrayBase<S2, Ix2>> for array::ArrayBase<S, Ix1>
+where
+    S: ndarray::Data<Elem = A>,
+    S2: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    type Output = array::Array1<A>;
+
+    fn dot(&self, rhs: &array::ArrayBase<S2, Ix2>) -> Result<array::Array1<A>, LinalgError> {
+        if self.len() != rhs.nrows() {
+            return Err(LinalgError::shape_mismatch(self.shape(), rhs.shape()));
+        }
+        let mut result = array::Array1::<A>::zeros(rhs.ncols());
+        for k in 0..self.len() {
+            for j in 0..rhs.ncols() {
+                result[j] = result[j] + self[k] * rhs[(k, j)];
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<A, S, S2> Dot<array::ArrayBase<S2, Ix2>> for array::ArrayBase<S, Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+    S2: ndarray::Data<Elem = A>,
+    A: num_traits::Float + num_traits::Zero + Send + Sync,
+{
+    type Output = array::Array2<A>;
+
+    fn dot(&self, rhs: &array::ArrayBase<S2, Ix2>) -> Result<array::Array2<A>, LinalgError> {
+        if self.ncols() != rhs.nrows() {
+            return Err(LinalgError::shape_mismatch(self.shape(), rhs.shape()));
+        }
+        if self.nrows() * self.ncols() * rhs.ncols() < PARALLEL_THRESHOLD {
+            Ok(matrix_matrix_dot(self, rhs))
+        } else {
+            Ok(matrix_matrix_dot_blocked(self, rhs))
+        }
+    }
+}
+
+// Below this many multiply-adds tiling and spawning tasks costs more than it saves
+const PARALLEL_THRESHOLD: usize = 64 * 64 * 64;
+const BLOCK_SIZE: usize = 64;
+
+fn matrix_matrix_dot<A, S, S2>(
+    lhs: &array::ArrayBase<S, Ix2>,
+    rhs: &array::ArrayBase<S2, Ix2>,
+) -> array::Array2<A>
+where
+    S: ndarray::Data<Elem = A>,
+    S2: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    let mut result = array::Array2::<A>::zeros((lhs.nrows(), rhs.ncols()));
+    for i in 0..lhs.nrows() {
+        for j in 0..rhs.ncols() {
+            let mut sum = A::zero();
+            for k in 0..lhs.ncols() {
+                sum = sum + lhs[(i, k)] * rhs[(k, j)];
             }
+            result[(i, j)] = sum;
         }
-        result
+    }
+    result
+}
+
+// The result is cut into BLOCK_SIZE² tiles and the inner dimension into blocks
+// of BLOCK_SIZE, both walked in parallel. Splitting the inner dimension is what
+// keeps XᵀX of a few features, which is a single tile, from running on one thread.
+fn matrix_matrix_dot_blocked<A, S, S2>(
+    lhs: &array::ArrayBase<S, Ix2>,
+    rhs: &array::ArrayBase<S2, Ix2>,
+) -> array::Array2<A>
+where
+    S: ndarray::Data<Elem = A>,
+    S2: ndarray::Data<Elem = A>,
+    A: num_traits::Float + Send + Sync,
+{
+    use ndarray::parallel::prelude::*;
+
+    let (m, inner, n) = (lhs.nrows(), lhs.ncols(), rhs.ncols());
+    let lhs = lhs.inner_impl().view();
+    let rhs = rhs.inner_impl().view();
+    let blocks = |len: usize| {
+        (0..len)
+            .step_by(BLOCK_SIZE)
+            .map(move |start| start..(start + BLOCK_SIZE).min(len))
+    };
+    let tiles: Vec<_> = blocks(m)
+        .flat_map(|rows| blocks(n).map(move |columns| (rows.clone(), columns)))
+        .collect();
+    let k_blocks: Vec<_> = blocks(inner).collect();
+
+    let products: Vec<Vec<A>> = tiles
+        .par_iter()
+        .map(|(rows, columns)| {
+            let (height, width) = (rows.len(), columns.len());
+            k_blocks
+                .par_iter()
+                .fold(
+                    || vec![A::zero(); height * width],
+                    |mut tile, ks| {
+                        // contiguous copies of both blocks, so that the inner loop runs over slices
+                        let a: Vec<A> = lhs
+                            .slice(ndarray::s![rows.clone(), ks.clone()])
+                            .iter()
+                            .copied()
+                            .collect();
+                        let b: Vec<A> = rhs
+                            .slice(ndarray::s![ks.clone(), columns.clone()])
+                            .iter()
+                            .copied()
+                            .collect();
+                        for (tile_row, a_row) in
+                            tile.chunks_exact_mut(width).zip(a.chunks_exact(ks.len()))
+                        {
+                            for (&a, b_row) in a_row.iter().zip(b.chunks_exact(width)) {
+                                for (out, &b) in tile_row.iter_mut().zip(b_row) {
+                                    *out = *out + a * b;
+                                }
+                            }
+                        }
+                        tile
+                    },
+                )
+                .reduce(
+                    || vec![A::zero(); height * width],
+                    |mut sum, tile| {
+                        for (sum, value) in sum.iter_mut().zip(tile) {
+                            *sum = *sum + value;
+                        }
+                        sum
+                    },
+                )
+        })
+        .collect();
+
+    let mut result = array::Array2::<A>::zeros((m, n));
+    for ((rows, columns), product) in tiles.into_iter().zip(products) {
+        let width = columns.len();
+        let mut block = result
+            .inner_impl_mut()
+            .slice_mut(ndarray::s![rows, columns]);
+        for (mut row, values) in block
+            .rows_mut()
+            .into_iter()
+            .zip(product.chunks_exact(width))
+        {
+            for (out, &value) in row.iter_mut().zip(values) {
+                *out = value;
+            }
+        }
+    }
+    result
+}
+
+impl<'a, 'b, A, O1, O2> Dot<NDArrayView<'b, A, NDArrayStorage<O2, 2>>>
+    for NDArrayView<'a, A, NDArrayStorage<O1, 2>>
+where
+    A: num_traits::Float,
+    O1: Order,
+    O2: Order,
+{
+    type Output = NDArray<A, NDArrayStorage<O1, 2>>;
+
+    fn dot(
+        &self,
+        rhs: &NDArrayView<'b, A, NDArrayStorage<O2, 2>>,
+    ) -> Result<NDArray<A, NDArrayStorage<O1, 2>>, LinalgError> {
+        let [m, inner] = *self.dimensions();
+        let [rhs_rows, n] = *rhs.dimensions();
+        if inner != rhs_rows {
+            return Err(LinalgError::shape_mismatch(
+                self.dimensions(),
+                rhs.dimensions(),
+            ));
+        }
+        let [lhs_row_stride, lhs_col_stride] = *self.layout().strides();
+        let [rhs_row_stride, rhs_col_stride] = *rhs.layout().strides();
+        let (lhs, rhs) = (self.as_slice(), rhs.as_slice());
+        let mut result = NDArray::<A, NDArrayStorage<O1, 2>>::new([m, n], A::zero());
+        let [result_row_stride, result_col_stride] = *result.layout().strides();
+        let output = result.as_mut_slice();
+        for i in 0..m {
+            for k in 0..inner {
+                let a = lhs[i * lhs_row_stride + k * lhs_col_stride];
+                for j in 0..n {
+                    let position = i * result_row_stride + j * result_col_stride;
+                    output[position] =
+                        output[position] + a * rhs[k * rhs_row_stride + j * rhs_col_stride];
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<'a, 'b, A, O1, O2> Dot<NDArrayView<'b, A, NDArrayStorage<O2, 1>>>
+    for NDArrayView<'a, A, NDArrayStorage<O1, 2>>
+where
+    A: num_traits::Float,
+    O1: Order,
+    O2: Order,
+{
+    type Output = NDArray<A, RowMajorStorage<1>>;
+
+    fn dot(
+        &self,
+        rhs: &NDArrayView<'b, A, NDArrayStorage<O2, 1>>,
+    ) -> Result<NDArray<A, RowMajorStorage<1>>, LinalgError> {
+        let [m, inner] = *self.dimensions();
+        if inner != rhs.len() {
+            return Err(LinalgError::shape_mismatch(
+                self.dimensions(),
+                rhs.dimensions(),
+            ));
+        }
+        let [row_stride, col_stride] = *self.layout().strides();
+        let (lhs, rhs) = (self.as_slice(), rhs.as_slice());
+        let mut result = NDArray::<A, RowMajorStorage<1>>::new([m], A::zero());
+        for (i, value) in result.as_mut_slice().iter_mut().enumerate() {
+            for (k, b) in rhs.iter().enumerate() {
+                *value = *value + lhs[i * row_stride + k * col_stride] * *b;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<A, O1, O2> Dot<NDArray<A, NDArrayStorage<O2, 2>>> for NDArray<A, NDArrayStorage<O1, 2>>
+where
+    A: num_traits::Float,
+    O1: Order,
+    O2: Order,
+{
+    type Output = NDArray<A, NDArrayStorage<O1, 2>>;
+
+    fn dot(
+        &self,
+        rhs: &NDArray<A, NDArrayStorage<O2, 2>>,
+    ) -> Result<NDArray<A, NDArrayStorage<O1, 2>>, LinalgError> {
+        self.view().dot(&rhs.view())
     }
 }
 
@@ -54,12 +308,13 @@ mod tests {
     use super::Dot;
     use crate::array;
     use crate::array::Transpose;
+    use crate::linalg::LinalgError;
 
     #[test]
     fn test_vector_vector_dot() {
         let a = array![1.0, 2.0, 3.0];
         let b = array![4.0, 5.0, 6.0];
-        let result = a.dot(&b);
+        let result = a.dot(&b).unwrap();
         assert_eq!(result, 32.0);
     }
 
@@ -67,7 +322,7 @@ mod tests {
     fn test_matrix_matrix_dot() {
         let a = array![[1.0, 2.0], [3.0, 4.0]];
         let b = array![[5.0, 6.0], [7.0, 8.0]];
-        let result = a.dot(&b);
+        let result = a.dot(&b).unwrap();
         assert_eq!(result, array![[19.0, 22.0], [43.0, 50.0]]);
     }
 
@@ -75,7 +330,7 @@ mod tests {
     fn test_row_vector_matrix_dot() {
         let a = array![[1.0, 2.0]];
         let b = array![[3.0, 4.0], [5.0, 6.0]];
-        let result = a.dot(&b);
+        let result = a.dot(&b).unwrap();
         assert_eq!(result, array![[13.0, 16.0]]);
     }
 
@@ -83,9 +338,82 @@ mod tests {
     fn test_matrix_column_vector_dot() {
         let a = array![[1.0, 2.0], [3.0, 4.0]];
         let b = array![[5.0, 6.0]];
-        let result = a.dot(&b.t());
+        let result = a.dot(&b.t()).unwrap();
 
         let expected_result = array![[17.0, 39.0]];
         assert_eq!(result, expected_result.t());
     }
+
+    #[test]
+    fn test_matrix_vector_dot() {
+        let a = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let b = array![7.0, 8.0];
+        assert_eq!(a.dot(&b).unwrap(), array![23.0, 53.0, 83.0]);
+    }
+
+    #[test]
+    fn test_vector_matrix_dot() {
+        let a = array![1.0, 2.0, 3.0];
+        let b = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        assert_eq!(a.dot(&b).unwrap(), array![22.0, 28.0]);
+        assert_eq!(a.dot(&b).unwrap(), b.t().dot(&a).unwrap());
+    }
+
+    #[test]
+    fn test_shape_mismatch() {
+        let a = array![[1.0, 2.0], [3.0, 4.0]];
+        let b = array![[5.0, 6.0]];
+        assert_eq!(
+            a.dot(&b),
+            Err(LinalgError::ShapeMismatch {
+                lhs: vec![2, 2],
+                rhs: vec![1, 2]
+            })
+        );
+        assert!(array![1.0, 2.0].dot(&array![1.0]).is_err());
+        assert!(a.dot(&array![1.0, 2.0, 3.0]).is_err());
+        assert!(array![1.0, 2.0, 3.0].dot(&a).is_err());
+    }
+
+    #[test]
+    fn test_blocked_matches_naive() {
+        let (m, k, n) = (150, 70, 90);
+        let a = array::Array2::from_shape_vec(
+            (m, k),
+            (0..m * k)
+                .map(|i| ((i * 7919) % 101) as f64 / 13.0 - 3.0)
+                .collect(),
+        )
+        .unwrap();
+        let b = array::Array2::from_shape_vec(
+            (k, n),
+            (0..k * n)
+                .map(|i| ((i * 104729) % 97) as f64 / 11.0 - 4.0)
+                .collect(),
+        )
+        .unwrap();
+        let expected = super::matrix_matrix_dot(&a, &b);
+        let result = a.dot(&b).unwrap();
+        for (x, y) in result.into_iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9);
+        }
+        let result_t = b.t().dot(&a.t()).unwrap();
+        for i in 0..n {
+            for j in 0..m {
+                assert!((result_t[(i, j)] - expected[(j, i)]).abs() < 1e-9);
+            }
+        }
+
+        // XᵀX of a tall X is a single tile with many blocks of the inner dimension
+        let x = array::Array2::from_shape_vec(
+            (5000, 8),
+            (0..5000 * 8)
+                .map(|i| ((i * 31) % 17) as f64 - 8.0)
+                .collect(),
+        )
+        .unwrap();
+        let expected = super::matrix_matrix_dot(&x.t(), &x);
+        assert_eq!(super::matrix_matrix_dot_blocked(&x.t(), &x), expected);
+        assert_eq!(x.t().dot(&x).unwrap(), expected);
+    }
 }
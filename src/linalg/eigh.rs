@@ -0,0 +1,175 @@
+use crate::array;
+use crate::linalg::error::LinalgError;
+
+const MAX_SWEEPS: usize = 64;
+
+// A = VΛVᵀ with eigenvalues in ascending order,
+// the i-th column of `eigenvectors` belongs to the i-th eigenvalue
+pub struct SymmetricEigen<A> {
+    pub eigenvalues: array::Array1<A>,
+    pub eigenvectors: array::Array2<A>,
+}
+
+pub trait Eigh<S, A>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    fn eigh(&self) -> Result<SymmetricEigen<A>, LinalgError>;
+}
+
+impl<S, A> Eigh<S, A> for array::ArrayBase<S, array::Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    // Cyclic Jacobi method: sweeps over the off-diagonal elements zeroing each one
+    // with a plane rotation A ← JᵀAJ until the off-diagonal part vanishes
+    fn eigh(&self) -> Result<SymmetricEigen<A>, LinalgError> {
+        if self.nrows() != self.ncols() {
+            return Err(LinalgError::NotSquare {
+                rows: self.nrows(),
+                cols: self.ncols(),
+            });
+        }
+        let n = self.nrows();
+        let norm = self.into_iter().fold(A::zero(), |norm, a| norm.hypot(*a));
+        let tolerance = norm * A::epsilon();
+        for i in 0..n {
+            for j in 0..i {
+                if (self[(i, j)] - self[(j, i)]).abs() > tolerance {
+                    return Err(LinalgError::NotSymmetric { row: i, col: j });
+                }
+            }
+        }
+
+        let mut a = array::Array2::<A>::zeros((n, n));
+        a.assign(self);
+        let mut v = array::Array2::<A>::zeros((n, n));
+        for i in 0..n {
+            v[(i, i)] = A::one();
+        }
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut off_diagonal = A::zero();
+            for p in 0..n {
+                for q in p + 1..n {
+                    off_diagonal = off_diagonal.hypot(a[(p, q)]);
+                }
+            }
+            if off_diagonal <= tolerance {
+                converged = true;
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)] == A::zero() {
+                        continue;
+                    }
+                    let two = A::one() + A::one();
+                    let theta = (a[(q, q)] - a[(p, p)]) / (two * a[(p, q)]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + A::one()).sqrt());
+                    let c = A::one() / (t * t + A::one()).sqrt();
+                    let s = c * t;
+                    for k in 0..n {
+                        let (a_kp, a_kq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * a_kp - s * a_kq;
+                        a[(k, q)] = s * a_kp + c * a_kq;
+                    }
+                    for k in 0..n {
+                        let (a_pk, a_qk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * a_pk - s * a_qk;
+                        a[(q, k)] = s * a_pk + c * a_qk;
+                    }
+                    a[(p, q)] = A::zero();
+                    a[(q, p)] = A::zero();
+                    for k in 0..n {
+                        let (v_kp, v_kq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * v_kp - s * v_kq;
+                        v[(k, q)] = s * v_kp + c * v_kq;
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(LinalgError::NoConvergence {
+                iterations: MAX_SWEEPS,
+            });
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| {
+            a[(*i, *i)]
+                .partial_cmp(&a[(*j, *j)])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut eigenvalues = array::Array1::<A>::zeros(n);
+        let mut eigenvectors = array::Array2::<A>::zeros((n, n));
+        for (k, j) in order.into_iter().enumerate() {
+            eigenvalues[k] = a[(j, j)];
+            for i in 0..n {
+                eigenvectors[(i, k)] = v[(i, j)];
+            }
+        }
+        Ok(SymmetricEigen {
+            eigenvalues,
+            eigenvectors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Eigh;
+    use crate::array;
+    use crate::array::Transpose;
+    use crate::linalg::Dot;
+    use crate::linalg::LinalgError;
+
+    #[test]
+    fn test_eigh_decomposition() {
+        let a: array::Array2<f64> = array![[4.0, 1.0, -2.0], [1.0, 2.0, 0.0], [-2.0, 0.0, 3.0]];
+        let eigen = a.eigh().unwrap();
+        let v = &eigen.eigenvectors;
+        for k in 1..3 {
+            assert!(eigen.eigenvalues[k - 1] <= eigen.eigenvalues[k]);
+        }
+        // AV = VΛ
+        let av = a.dot(v).unwrap();
+        for i in 0..3 {
+            for k in 0..3 {
+                assert!((av[(i, k)] - v[(i, k)] * eigen.eigenvalues[k]).abs() < 1e-10);
+            }
+        }
+        let vtv = v.t().dot(v).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((vtv[(i, j)] - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigh_known_values() {
+        let a: array::Array2<f64> = array![[2.0, 1.0], [1.0, 2.0]];
+        let eigen = a.eigh().unwrap();
+        assert!((eigen.eigenvalues[0] - 1.0).abs() < 1e-12);
+        assert!((eigen.eigenvalues[1] - 3.0).abs() < 1e-12);
+        assert!((eigen.eigenvectors[(0, 1)].abs() - 0.5_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_eigh_errors() {
+        let not_symmetric = array![[1.0, 2.0], [3.0, 4.0]];
+        assert_eq!(
+            not_symmetric.eigh().err(),
+            Some(LinalgError::NotSymmetric { row: 1, col: 0 })
+        );
+        let not_square = array![[1.0, 2.0]];
+        assert_eq!(
+            not_square.eigh().err(),
+            Some(LinalgError::NotSquare { rows: 1, cols: 2 })
+        );
+    }
+}
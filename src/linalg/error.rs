@@ -1,3 +1,52 @@
-//pub enum LinalgError {
-//    ShapeError,
-//}
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinalgError {
+    NotSquare { rows: usize, cols: usize },
+    NotSymmetric { row: usize, col: usize },
+    // index of the diagonal element at which the factorization broke down
+    NotPositiveDefinite { pivot: usize },
+    ShapeMismatch { lhs: Vec<usize>, rhs: Vec<usize> },
+    // index of the diagonal element of a triangular factor that is numerically zero
+    Singular { pivot: usize },
+    Underdetermined { rows: usize, cols: usize },
+    NoConvergence { iterations: usize },
+}
+
+impl LinalgError {
+    pub(crate) fn shape_mismatch(lhs: &[usize], rhs: &[usize]) -> Self {
+        LinalgError::ShapeMismatch {
+            lhs: lhs.to_vec(),
+            rhs: rhs.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "{}x{} matrix is not square", rows, cols)
+            }
+            LinalgError::NotSymmetric { row, col } => {
+                write!(f, "matrix is not symmetric at ({}, {})", row, col)
+            }
+            LinalgError::NotPositiveDefinite { pivot } => {
+                write!(f, "matrix is not positive definite at pivot {}", pivot)
+            }
+            LinalgError::ShapeMismatch { lhs, rhs } => {
+                write!(f, "shapes {:?} and {:?} do not match", lhs, rhs)
+            }
+            LinalgError::Singular { pivot } => write!(f, "matrix is singular at pivot {}", pivot),
+            LinalgError::Underdetermined { rows, cols } => {
+                write!(f, "{}x{} system is underdetermined", rows, cols)
+            }
+            LinalgError::NoConvergence { iterations } => {
+                write!(f, "no convergence after {} iterations", iterations)
+            }
+        }
+    }
+}
+
+impl Error for LinalgError {}
@@ -0,0 +1,217 @@
+use crate::array;
+use crate::linalg::error::LinalgError;
+
+// PA = LU stored in compact form: U on and above the diagonal,
+// L below it with an implicit unit diagonal
+pub struct LUMatrix<A> {
+    data: array::Array2<A>,
+    // row i of PA is row permutation[i] of A
+    permutation: Vec<usize>,
+    swap_count: usize,
+}
+
+impl<A> LUMatrix<A>
+where
+    A: num_traits::Float,
+{
+    // Solves Ax = b by forward substitution Ly = Pb followed by backward substitution Ux = y
+    pub fn solve<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix1>,
+    ) -> Result<array::Array1<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        let n = self.data.nrows();
+        if b.len() != n {
+            return Err(LinalgError::shape_mismatch(self.data.shape(), b.shape()));
+        }
+        self.check_singular()?;
+
+        let mut y = array::Array1::<A>::zeros(n);
+        for i in 0..n {
+            let mut sum = A::zero();
+            for k in 0..i {
+                sum = sum + self.data[(i, k)] * y[k];
+            }
+            y[i] = b[self.permutation[i]] - sum;
+        }
+
+        let mut x = array::Array1::<A>::zeros(n);
+        for i in (0..n).rev() {
+            let mut sum = A::zero();
+            for k in i + 1..n {
+                sum = sum + self.data[(i, k)] * x[k];
+            }
+            x[i] = (y[i] - sum) / self.data[(i, i)];
+        }
+        Ok(x)
+    }
+
+    // Solves AX = B column by column
+    pub fn solve_many<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix2>,
+    ) -> Result<array::Array2<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        let n = self.data.nrows();
+        if b.nrows() != n {
+            return Err(LinalgError::shape_mismatch(self.data.shape(), b.shape()));
+        }
+        let mut result = array::Array2::<A>::zeros((n, b.ncols()));
+        for j in 0..b.ncols() {
+            let b_j: array::Array1<A> = (0..n).map(|i| b[(i, j)]).collect();
+            result.column_mut(j).assign(&self.solve(&b_j)?);
+        }
+        Ok(result)
+    }
+
+    pub fn inv(&self) -> Result<array::Array2<A>, LinalgError> {
+        let n = self.data.nrows();
+        let mut identity = array::Array2::<A>::zeros((n, n));
+        for i in 0..n {
+            identity[(i, i)] = A::one();
+        }
+        self.solve_many(&identity)
+    }
+
+    // det(A) = det(P)·∏uᵢᵢ, zero for singular matrices
+    pub fn det(&self) -> A {
+        let product = (0..self.data.nrows()).fold(A::one(), |det, i| det * self.data[(i, i)]);
+        if self.swap_count.is_multiple_of(2) {
+            product
+        } else {
+            -product
+        }
+    }
+
+    // U is treated as singular when a pivot is negligible compared to the largest one
+    fn check_singular(&self) -> Result<(), LinalgError> {
+        let n = self.data.nrows();
+        let max_pivot = (0..n)
+            .map(|i| self.data[(i, i)].abs())
+            .fold(A::zero(), A::max);
+        let tolerance = max_pivot * A::epsilon() * A::from(n).unwrap_or(A::one());
+        for i in 0..n {
+            if self.data[(i, i)].abs() <= tolerance {
+                return Err(LinalgError::Singular { pivot: i });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub trait LU<S, A>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    fn lu(&self) -> Result<LUMatrix<A>, LinalgError>;
+}
+
+impl<S, A> LU<S, A> for array::ArrayBase<S, array::Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    // Doolittle elimination, swapping the row with the largest pivot candidate into place
+    fn lu(&self) -> Result<LUMatrix<A>, LinalgError> {
+        if self.nrows() != self.ncols() {
+            return Err(LinalgError::NotSquare {
+                rows: self.nrows(),
+                cols: self.ncols(),
+            });
+        }
+        let n = self.nrows();
+        let mut data = array::Array2::<A>::zeros((n, n));
+        data.assign(self);
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swap_count = 0;
+        for k in 0..n {
+            let mut pivot_row = k;
+            for i in k + 1..n {
+                if data[(i, k)].abs() > data[(pivot_row, k)].abs() {
+                    pivot_row = i;
+                }
+            }
+            if pivot_row != k {
+                for j in 0..n {
+                    let tmp = data[(k, j)];
+                    data[(k, j)] = data[(pivot_row, j)];
+                    data[(pivot_row, j)] = tmp;
+                }
+                permutation.swap(k, pivot_row);
+                swap_count += 1;
+            }
+            let pivot = data[(k, k)];
+            if pivot == A::zero() {
+                // the column is already zero below the diagonal
+                continue;
+            }
+            for i in k + 1..n {
+                let factor = data[(i, k)] / pivot;
+                data[(i, k)] = factor;
+                for j in k + 1..n {
+                    data[(i, j)] = data[(i, j)] - factor * data[(k, j)];
+                }
+            }
+        }
+        Ok(LUMatrix {
+            data,
+            permutation,
+            swap_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LU;
+    use crate::array;
+    use crate::linalg::LinalgError;
+
+    #[test]
+    fn test_lu_solve() {
+        // zero in the top left corner needs pivoting
+        let a: array::Array2<f64> = array![[0.0, 2.0, 1.0], [1.0, -2.0, -3.0], [-1.0, 1.0, 2.0]];
+        let x = array![1.0, 2.0, -1.0];
+        let b = array![3.0, 0.0, -1.0];
+        let result = a.lu().unwrap().solve(&b).unwrap();
+        for i in 0..3 {
+            assert!((result[i] - x[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_inverse_and_determinant() {
+        let a: array::Array2<f64> = array![[4.0, 7.0], [2.0, 6.0]];
+        let lu = a.lu().unwrap();
+        assert!((lu.det() - 10.0).abs() < 1e-12);
+        let inverse = lu.inv().unwrap();
+        let expected = array![[0.6, -0.7], [-0.2, 0.4]];
+        for (x, y) in inverse.into_iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12);
+        }
+
+        let swapped: array::Array2<f64> = array![[2.0, 6.0], [4.0, 7.0]];
+        assert!((swapped.lu().unwrap().det() + 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lu_singular() {
+        let a: array::Array2<f64> = array![[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]];
+        let lu = a.lu().unwrap();
+        assert_eq!(lu.det(), 0.0);
+        assert_eq!(
+            lu.solve(&array![1.0, 2.0, 3.0]).err(),
+            Some(LinalgError::Singular { pivot: 2 })
+        );
+        assert!(lu.inv().is_err());
+        assert_eq!(
+            array![[1.0, 2.0]].lu().err(),
+            Some(LinalgError::NotSquare { rows: 1, cols: 2 })
+        );
+    }
+}
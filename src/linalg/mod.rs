@@ -2,9 +2,17 @@ pub mod arithmetic;
 pub mod array_base;
 pub mod cholesky;
 pub mod dot;
+pub mod eigh;
 pub mod error;
+pub mod lu;
+pub mod qr;
+pub mod svd;
 
 pub use crate::linalg::arithmetic::*;
 pub use crate::linalg::cholesky::*;
 pub use crate::linalg::dot::*;
+pub use crate::linalg::eigh::*;
 pub use crate::linalg::error::*;
+pub use crate::linalg::lu::*;
+pub use crate::linalg::qr::*;
+pub use crate::linalg::svd::*;
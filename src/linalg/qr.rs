@@ -0,0 +1,241 @@
+use crate::array;
+use crate::linalg::error::LinalgError;
+
+// A = QR stored in compact form: R on and above the diagonal, the Householder vectors
+// (with an implicit leading one) below it
+pub struct QRMatrix<A> {
+    data: array::Array2<A>,
+    tau: array::Array1<A>,
+}
+
+impl<A> QRMatrix<A>
+where
+    A: num_traits::Float,
+{
+    // Thin Q with orthonormal columns, m × min(m, n)
+    pub fn q(&self) -> array::Array2<A> {
+        let (m, k) = (self.data.nrows(), self.tau.len());
+        let mut q = array::Array2::<A>::zeros((m, k));
+        for i in 0..k {
+            q[(i, i)] = A::one();
+        }
+        // Q = H₀H₁…Hₖ₋₁I
+        for j in (0..k).rev() {
+            for c in 0..k {
+                self.reflect(j, &mut q.column_mut(c));
+            }
+        }
+        q
+    }
+
+    // Upper triangular R, min(m, n) × n
+    pub fn r(&self) -> array::Array2<A> {
+        let (n, k) = (self.data.ncols(), self.tau.len());
+        let mut r = array::Array2::<A>::zeros((k, n));
+        for i in 0..k {
+            for j in i..n {
+                r[(i, j)] = self.data[(i, j)];
+            }
+        }
+        r
+    }
+
+    // Least-squares solution of Ax = b, i.e. Rx = Qᵀb
+    pub fn solve<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix1>,
+    ) -> Result<array::Array1<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        let (m, n) = (self.data.nrows(), self.data.ncols());
+        if b.len() != m {
+            return Err(LinalgError::shape_mismatch(self.data.shape(), b.shape()));
+        }
+        if m < n {
+            return Err(LinalgError::Underdetermined { rows: m, cols: n });
+        }
+        let mut qt_b: array::Array1<A> = b.into_iter().copied().collect();
+        for j in 0..n {
+            self.reflect(j, &mut qt_b);
+        }
+        self.check_rank()?;
+
+        let mut x = array::Array1::<A>::zeros(n);
+        for i in (0..n).rev() {
+            let mut sum = A::zero();
+            for k in i + 1..n {
+                sum = sum + self.data[(i, k)] * x[k];
+            }
+            x[i] = (qt_b[i] - sum) / self.data[(i, i)];
+        }
+        Ok(x)
+    }
+
+    // Least-squares solution of AX = B column by column
+    pub fn solve_many<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix2>,
+    ) -> Result<array::Array2<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        if b.nrows() != self.data.nrows() {
+            return Err(LinalgError::shape_mismatch(self.data.shape(), b.shape()));
+        }
+        let mut result = array::Array2::<A>::zeros((self.data.ncols(), b.ncols()));
+        for j in 0..b.ncols() {
+            let b_j: array::Array1<A> = (0..b.nrows()).map(|i| b[(i, j)]).collect();
+            result.column_mut(j).assign(&self.solve(&b_j)?);
+        }
+        Ok(result)
+    }
+
+    // R is treated as singular when a diagonal element is negligible compared to the largest one
+    fn check_rank(&self) -> Result<(), LinalgError> {
+        let (m, n) = (self.data.nrows(), self.data.ncols());
+        let max_diagonal = (0..n)
+            .map(|i| self.data[(i, i)].abs())
+            .fold(A::zero(), A::max);
+        let tolerance = max_diagonal * A::epsilon() * A::from(m.max(n)).unwrap_or(A::one());
+        for i in 0..n {
+            if self.data[(i, i)].abs() <= tolerance {
+                return Err(LinalgError::Singular { pivot: i });
+            }
+        }
+        Ok(())
+    }
+
+    // Applies Hⱼ = I - τvvᵀ in place, where v is the j-th Householder vector
+    fn reflect<S>(&self, j: usize, x: &mut array::ArrayBase<S, array::Ix1>)
+    where
+        S: ndarray::DataMut<Elem = A>,
+    {
+        let m = self.data.nrows();
+        let mut w = x[j];
+        for i in j + 1..m {
+            w = w + self.data[(i, j)] * x[i];
+        }
+        w = w * self.tau[j];
+        x[j] = x[j] - w;
+        for i in j + 1..m {
+            x[i] = x[i] - w * self.data[(i, j)];
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub trait QR<S, A>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    fn qr(&self) -> QRMatrix<A>;
+}
+
+impl<S, A> QR<S, A> for array::ArrayBase<S, array::Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    fn qr(&self) -> QRMatrix<A> {
+        let (m, n) = (self.nrows(), self.ncols());
+        let k = m.min(n);
+        let mut data = array::Array2::<A>::zeros((m, n));
+        data.assign(self);
+        let mut tau = array::Array1::<A>::zeros(k);
+        for j in 0..k {
+            let alpha = data[(j, j)];
+            let mut norm = A::zero();
+            for i in j..m {
+                norm = norm.hypot(data[(i, j)]);
+            }
+            if norm == A::zero() {
+                continue;
+            }
+            // reflect onto -sign(α)‖x‖e₁ to avoid cancellation in α - β
+            let beta = if alpha > A::zero() { -norm } else { norm };
+            let scale = alpha - beta;
+            for i in j + 1..m {
+                data[(i, j)] = data[(i, j)] / scale;
+            }
+            tau[j] = (beta - alpha) / beta;
+            data[(j, j)] = beta;
+
+            for c in j + 1..n {
+                let mut w = data[(j, c)];
+                for i in j + 1..m {
+                    w = w + data[(i, j)] * data[(i, c)];
+                }
+                w = w * tau[j];
+                data[(j, c)] = data[(j, c)] - w;
+                for i in j + 1..m {
+                    data[(i, c)] = data[(i, c)] - w * data[(i, j)];
+                }
+            }
+        }
+        QRMatrix { data, tau }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QR;
+    use crate::array;
+    use crate::array::Transpose;
+    use crate::linalg::Dot;
+    use crate::linalg::LinalgError;
+
+    fn assert_close(a: &array::Array2<f64>, b: &array::Array2<f64>) {
+        assert_eq!(a.shape(), b.shape());
+        for (x, y) in a.into_iter().zip(b) {
+            assert!((x - y).abs() < 1e-10, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_qr_decomposition() {
+        let a = array![
+            [12.0, -51.0, 4.0],
+            [6.0, 167.0, -68.0],
+            [-4.0, 24.0, -41.0],
+            [1.0, 1.0, 1.0]
+        ];
+        let qr = a.qr();
+        let q = qr.q();
+        let r = qr.r();
+        assert_close(&q.dot(&r).unwrap(), &a);
+        assert_close(
+            &q.t().dot(&q).unwrap(),
+            &array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(r[(i, j)], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qr_least_squares() {
+        // best fit line through the points is y = 1.3 + 1.8x
+        let a = array![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0], [1.0, 3.0]];
+        let b = array![1.5_f64, 2.5, 5.5, 6.5];
+        let x = a.qr().solve(&b).unwrap();
+        assert!((x[0] - 1.3).abs() < 1e-12);
+        assert!((x[1] - 1.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_qr_errors() {
+        let collinear = array![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+        assert_eq!(
+            collinear.qr().solve(&array![1.0, 2.0, 3.0]).err(),
+            Some(LinalgError::Singular { pivot: 1 })
+        );
+        let wide = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        assert_eq!(
+            wide.qr().solve(&array![1.0, 2.0]).err(),
+            Some(LinalgError::Underdetermined { rows: 2, cols: 3 })
+        );
+    }
+}
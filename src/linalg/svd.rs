@@ -0,0 +1,307 @@
+use crate::array;
+use crate::array::Transpose;
+use crate::linalg::error::LinalgError;
+
+const MAX_SWEEPS: usize = 64;
+
+// A = UΣVᵀ with singular values in descending order,
+// U is m × k, Σ has k entries and Vᵀ is k × n where k = min(m, n)
+pub struct SVDMatrix<A> {
+    u: array::Array2<A>,
+    s: array::Array1<A>,
+    vt: array::Array2<A>,
+}
+
+impl<A> SVDMatrix<A>
+where
+    A: num_traits::Float,
+{
+    pub fn u(&self) -> array::ArrayView2<'_, A> {
+        self.u.view()
+    }
+
+    pub fn s(&self) -> array::ArrayView1<'_, A> {
+        self.s.view()
+    }
+
+    pub fn vt(&self) -> array::ArrayView2<'_, A> {
+        self.vt.view()
+    }
+
+    // Singular values below max(m, n)·ε·σ₁ are treated as zero
+    pub fn tolerance(&self) -> A {
+        let size = self.u.nrows().max(self.vt.ncols());
+        let largest = if self.s.is_empty() {
+            A::zero()
+        } else {
+            self.s[0]
+        };
+        A::from(size).unwrap_or(A::one()) * A::epsilon() * largest
+    }
+
+    // Number of singular values above the tolerance
+    pub fn rank(&self) -> usize {
+        let tolerance = self.tolerance();
+        self.s.into_iter().filter(|s| **s > tolerance).count()
+    }
+
+    // σ₁ / σₖ, infinite for rank deficient matrices
+    pub fn condition_number(&self) -> A {
+        match self.s.len() {
+            0 => A::infinity(),
+            k if self.s[k - 1] == A::zero() => A::infinity(),
+            k => self.s[0] / self.s[k - 1],
+        }
+    }
+
+    // Moore–Penrose pseudo-inverse A⁺ = VΣ⁺Uᵀ
+    pub fn pinv(&self) -> array::Array2<A> {
+        let (m, n) = (self.u.nrows(), self.vt.ncols());
+        let inverted = self.inverted_singular_values();
+        let mut result = array::Array2::<A>::zeros((n, m));
+        for i in 0..n {
+            for j in 0..m {
+                let mut sum = A::zero();
+                for k in 0..inverted.len() {
+                    sum = sum + self.vt[(k, i)] * inverted[k] * self.u[(j, k)];
+                }
+                result[(i, j)] = sum;
+            }
+        }
+        result
+    }
+
+    // Minimum norm least-squares solution x = A⁺b
+    pub fn solve<S>(
+        &self,
+        b: &array::ArrayBase<S, array::Ix1>,
+    ) -> Result<array::Array1<A>, LinalgError>
+    where
+        S: ndarray::Data<Elem = A>,
+    {
+        let (m, n) = (self.u.nrows(), self.vt.ncols());
+        if b.len() != m {
+            return Err(LinalgError::shape_mismatch(&[m, n], b.shape()));
+        }
+        let inverted = self.inverted_singular_values();
+        // Σ⁺Uᵀb
+        let projected: array::Array1<A> = (0..inverted.len())
+            .map(|k| {
+                let mut sum = A::zero();
+                for i in 0..m {
+                    sum = sum + self.u[(i, k)] * b[i];
+                }
+                sum * inverted[k]
+            })
+            .collect();
+        Ok((0..n)
+            .map(|i| {
+                let mut sum = A::zero();
+                for k in 0..projected.len() {
+                    sum = sum + self.vt[(k, i)] * projected[k];
+                }
+                sum
+            })
+            .collect())
+    }
+
+    fn inverted_singular_values(&self) -> array::Array1<A> {
+        let tolerance = self.tolerance();
+        self.s
+            .into_iter()
+            .map(|s| {
+                if *s > tolerance {
+                    A::one() / *s
+                } else {
+                    A::zero()
+                }
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub trait SVD<S, A>
+where
+    S: ndarray::Data<Elem = A>,
+{
+    fn svd(&self) -> Result<SVDMatrix<A>, LinalgError>;
+}
+
+impl<S, A> SVD<S, A> for array::ArrayBase<S, array::Ix2>
+where
+    S: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    fn svd(&self) -> Result<SVDMatrix<A>, LinalgError> {
+        if self.nrows() >= self.ncols() {
+            one_sided_jacobi(self)
+        } else {
+            // Aᵀ = VΣUᵀ
+            let svd = one_sided_jacobi(&self.t())?;
+            Ok(SVDMatrix {
+                u: transposed(&svd.vt),
+                s: svd.s,
+                vt: transposed(&svd.u),
+            })
+        }
+    }
+}
+
+// Hestenes' method: orthogonalizes the columns of A by plane rotations accumulated in V,
+// so that AV = UΣ. Requires m ≥ n.
+fn one_sided_jacobi<S, A>(a: &array::ArrayBase<S, array::Ix2>) -> Result<SVDMatrix<A>, LinalgError>
+where
+    S: ndarray::Data<Elem = A>,
+    A: num_traits::Float,
+{
+    let (m, n) = (a.nrows(), a.ncols());
+    let mut u = array::Array2::<A>::zeros((m, n));
+    u.assign(a);
+    let mut v = array::Array2::<A>::zeros((n, n));
+    for i in 0..n {
+        v[(i, i)] = A::one();
+    }
+
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta, mut gamma) = (A::zero(), A::zero(), A::zero());
+                for i in 0..m {
+                    alpha = alpha + u[(i, p)] * u[(i, p)];
+                    beta = beta + u[(i, q)] * u[(i, q)];
+                    gamma = gamma + u[(i, p)] * u[(i, q)];
+                }
+                if gamma == A::zero() || gamma.abs() <= A::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let two = A::one() + A::one();
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = zeta.signum() / (zeta.abs() + (A::one() + zeta * zeta).sqrt());
+                let c = A::one() / (A::one() + t * t).sqrt();
+                let s = c * t;
+                rotate_columns(&mut u, p, q, c, s);
+                rotate_columns(&mut v, p, q, c, s);
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::NoConvergence {
+            iterations: MAX_SWEEPS,
+        });
+    }
+
+    let norms: Vec<A> = (0..n)
+        .map(|j| (0..m).fold(A::zero(), |norm, i| norm.hypot(u[(i, j)])))
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| {
+        norms[*j]
+            .partial_cmp(&norms[*i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut sorted_u = array::Array2::<A>::zeros((m, n));
+    let mut vt = array::Array2::<A>::zeros((n, n));
+    let mut s = array::Array1::<A>::zeros(n);
+    for (k, j) in order.into_iter().enumerate() {
+        s[k] = norms[j];
+        for i in 0..m {
+            sorted_u[(i, k)] = if norms[j] > A::zero() {
+                u[(i, j)] / norms[j]
+            } else {
+                A::zero()
+            };
+        }
+        for i in 0..n {
+            vt[(k, i)] = v[(i, j)];
+        }
+    }
+    Ok(SVDMatrix { u: sorted_u, s, vt })
+}
+
+fn rotate_columns<A>(a: &mut array::Array2<A>, p: usize, q: usize, c: A, s: A)
+where
+    A: num_traits::Float,
+{
+    for i in 0..a.nrows() {
+        let (a_p, a_q) = (a[(i, p)], a[(i, q)]);
+        a[(i, p)] = c * a_p - s * a_q;
+        a[(i, q)] = s * a_p + c * a_q;
+    }
+}
+
+fn transposed<A>(a: &array::Array2<A>) -> array::Array2<A>
+where
+    A: num_traits::Float,
+{
+    let mut result = array::Array2::<A>::zeros((a.ncols(), a.nrows()));
+    result.assign(&a.t());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SVD;
+    use crate::array;
+    use crate::linalg::Dot;
+
+    fn assert_close(a: &array::Array2<f64>, b: &array::Array2<f64>) {
+        assert_eq!(a.shape(), b.shape());
+        for (x, y) in a.into_iter().zip(b) {
+            assert!((x - y).abs() < 1e-10, "{:?} != {:?}", a, b);
+        }
+    }
+
+    fn reconstruct(a: &array::Array2<f64>) -> array::Array2<f64> {
+        let svd = a.svd().unwrap();
+        let mut us = array::Array2::<f64>::zeros((svd.u().nrows(), svd.s().len()));
+        for i in 0..us.nrows() {
+            for k in 0..us.ncols() {
+                us[(i, k)] = svd.u()[(i, k)] * svd.s()[k];
+            }
+        }
+        us.dot(&svd.vt()).unwrap()
+    }
+
+    #[test]
+    fn test_svd_decomposition() {
+        let tall = array![[3.0, 2.0], [2.0, 3.0], [2.0, -2.0]];
+        assert_close(&reconstruct(&tall), &tall);
+        let wide = array![[3.0, 2.0, 2.0], [2.0, 3.0, -2.0]];
+        assert_close(&reconstruct(&wide), &wide);
+
+        let svd = wide.svd().unwrap();
+        assert!((svd.s()[0] - 5.0).abs() < 1e-12);
+        assert!((svd.s()[1] - 3.0).abs() < 1e-12);
+        assert_eq!(svd.rank(), 2);
+        assert!((svd.condition_number() - 5.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_svd_rank_deficient() {
+        let a: array::Array2<f64> = array![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+        let svd = a.svd().unwrap();
+        assert_eq!(svd.rank(), 1);
+        assert!(svd.condition_number() > 1e12);
+
+        // A⁺ of a rank one matrix xyᵀ is yxᵀ / (‖x‖²‖y‖²)
+        let expected: array::Array2<f64> = array![[1.0, 2.0, 3.0], [2.0, 4.0, 6.0]];
+        let pinv = svd.pinv();
+        for (p, e) in pinv.into_iter().zip(&expected) {
+            assert!((p - e / 70.0).abs() < 1e-12);
+        }
+
+        // minimum norm solution lies along (1, 2)
+        let x = svd.solve(&array![1.0, 2.0, 3.0]).unwrap();
+        assert!((x[0] - 0.2).abs() < 1e-12);
+        assert!((x[1] - 0.4).abs() < 1e-12);
+    }
+}
@@ -1,11 +1,61 @@
 use crate::array;
+use crate::linalg::LinalgError;
+
+use std::error::Error;
+use std::fmt;
 
 #[derive(Debug)]
 pub enum LinearRegressionError {
     FailedCastToT,
     OperationFailed,
+    InvalidBatchSize,
+    EmptyDataset,
+    LinalgError(LinalgError),
+    // reading the training samples failed
+    DataSourceError(Box<dyn Error + Send + Sync>),
+}
+
+impl From<LinalgError> for LinearRegressionError {
+    fn from(error: LinalgError) -> Self {
+        LinearRegressionError::LinalgError(error)
+    }
+}
+
+impl fmt::Display for LinearRegressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinearRegressionError::FailedCastToT => {
+                write!(f, "value does not fit the element type")
+            }
+            LinearRegressionError::OperationFailed => write!(f, "array operation failed"),
+            LinearRegressionError::InvalidBatchSize => write!(f, "batch size must be at least 1"),
+            LinearRegressionError::EmptyDataset => write!(f, "no samples to train on"),
+            LinearRegressionError::LinalgError(error) => write!(f, "{}", error),
+            LinearRegressionError::DataSourceError(error) => {
+                write!(f, "reading the samples failed: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for LinearRegressionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LinearRegressionError::LinalgError(error) => Some(error),
+            LinearRegressionError::DataSourceError(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 pub trait LinearRegressionModel<T> {
     fn predict(&self, x: &array::ArrayView1<T>) -> Result<T, LinearRegressionError>;
+
+    // Predicts every row of x
+    fn predict_batch(
+        &self,
+        x: &array::ArrayView2<T>,
+    ) -> Result<array::Array1<T>, LinearRegressionError> {
+        (0..x.nrows()).map(|i| self.predict(&x.row(i))).collect()
+    }
 }
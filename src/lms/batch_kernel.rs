@@ -1,5 +1,5 @@
 use crate::array;
-use crate::linalg::arithmetic::Arithmetic;
+use crate::linalg::arithmetic::ArithmeticAssign;
 use crate::linalg::dot::Dot;
 use crate::lms::kernel::*;
 
@@ -16,25 +16,23 @@ where
         weight_function: F,
     ) -> Result<LMSResult<T>, LMSError>
     where
-        F: Fn(array::ArrayView1<T>) -> T,
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
     {
         let m = x.nrows();
-        let n = x.ncols();
         let mut iteration_count = 0;
         let mut previous_cost = T::zero();
         let mut theta = settings.starting_theta.clone();
         loop {
-            let mut gradients = array::Array1::<T>::zeros(n);
+            let mut errors = x.dot(&theta)?;
             let mut cost = T::zero();
             for i in 0..m {
-                let weight = weight_function(x.row(i));
-                let error = weight * x.row(i).dot(&theta) - y[i];
-                cost += error * error;
-                gradients = gradients.scaled_add(error, &x.row(i));
-            }
-            for i in 0..n {
-                theta[i] -= settings.learning_rate * gradients[i];
+                let weight = weight_function(x.row(i))?;
+                errors[i] = weight * errors[i] - y[i];
+                cost += errors[i] * errors[i];
             }
+            // ∇J = Xᵀ(Xθ - y)
+            let gradients = errors.dot(&x)?;
+            theta.scaled_add_assign(settings.learning_rate.neg(), &gradients)?;
             let cost_change = num::Float::abs(previous_cost - cost);
             let cost_change = cost_change / T::from(m).ok_or(LMSError::FailedCastToT)?;
             if cost_change < settings.eps {
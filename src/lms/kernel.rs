@@ -1,10 +1,15 @@
 use crate::array;
+use crate::data_source::DataSource;
+use crate::linalg::LinalgError;
+
+use std::error::Error;
 
 pub struct LMSSettingsFilled<T> {
     pub max_iteration_count: usize,
     pub learning_rate: T,
     pub eps: T,
     pub starting_theta: array::Array1<T>,
+    pub batch_size: usize,
 }
 
 pub struct LMSResult<T> {
@@ -15,6 +20,24 @@ pub struct LMSResult<T> {
 #[derive(Debug)]
 pub enum LMSError {
     FailedCastToT,
+    // a batch must hold at least one sample
+    InvalidBatchSize,
+    // the data had no samples to train on
+    EmptyDataset,
+    LinalgError(LinalgError),
+    DataSourceError(Box<dyn Error + Send + Sync>),
+}
+
+impl LMSError {
+    pub(crate) fn data_source<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        LMSError::DataSourceError(Box::new(error))
+    }
+}
+
+impl From<LinalgError> for LMSError {
+    fn from(error: LinalgError) -> Self {
+        LMSError::LinalgError(error)
+    }
 }
 
 pub trait Kernel<T> {
@@ -26,5 +49,19 @@ pub trait Kernel<T> {
     ) -> Result<LMSResult<T>, LMSError>
     where
         T: num_traits::Float + num_traits::NumAssignOps,
-        F: Fn(array::ArrayView1<T>) -> T;
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>;
+}
+
+// Kernels that only look at a few samples at a time, so that they can train
+// from a data source that does not fit in memory
+pub trait StreamingKernel<T> {
+    fn compute_streaming<D, F>(
+        source: &mut D,
+        settings: LMSSettingsFilled<T>,
+        weight_function: F,
+    ) -> Result<LMSResult<T>, LMSError>
+    where
+        T: num_traits::Float + num_traits::NumAssignOps,
+        D: DataSource<T>,
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>;
 }
@@ -0,0 +1,88 @@
+use crate::array;
+use crate::data_source::{ArraySource, DataSource};
+use crate::linalg::arithmetic::ArithmeticAssign;
+use crate::linalg::dot::Dot;
+use crate::lms::kernel::*;
+
+// Updates θ after every batch_size samples, between the batch and the
+// stochastic kernel in cost per update and noise of the updates
+pub struct MiniBatchKernel;
+
+impl<T> Kernel<T> for MiniBatchKernel
+where
+    T: num_traits::Float + num_traits::NumAssignOps + std::iter::Sum,
+{
+    fn compute<F>(
+        x: array::ArrayView2<T>,
+        y: array::ArrayView1<T>,
+        settings: LMSSettingsFilled<T>,
+        weight_function: F,
+    ) -> Result<LMSResult<T>, LMSError>
+    where
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
+    {
+        Self::compute_streaming(
+            &mut ArraySource::new(x.slice(.., ..), y.slice(..)),
+            settings,
+            weight_function,
+        )
+    }
+}
+
+impl<T> StreamingKernel<T> for MiniBatchKernel
+where
+    T: num_traits::Float + num_traits::NumAssignOps + std::iter::Sum,
+{
+    fn compute_streaming<D, F>(
+        source: &mut D,
+        settings: LMSSettingsFilled<T>,
+        weight_function: F,
+    ) -> Result<LMSResult<T>, LMSError>
+    where
+        D: DataSource<T>,
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
+    {
+        let mut iteration_count = 0;
+        let mut previous_cost = T::zero();
+        let mut theta = settings.starting_theta.clone();
+        let mut gradients = array::Array1::<T>::zeros(theta.len());
+        loop {
+            source.rewind().map_err(LMSError::data_source)?;
+            let mut m = 0;
+            let mut cost = T::zero();
+            while let Some(batch) = source
+                .next_batch(settings.batch_size)
+                .map_err(LMSError::data_source)?
+            {
+                gradients.fill(T::zero());
+                let batch_len = T::from(batch.x.nrows()).ok_or(LMSError::FailedCastToT)?;
+                for i in 0..batch.x.nrows() {
+                    let weight = weight_function(batch.x.row(i))?;
+                    let error = weight * (batch.x.row(i).dot(&theta)? - batch.y[i]);
+                    cost += error * error;
+                    // ∇J = Σ(xᵢθ - yᵢ)xᵢ / b over the b samples of the batch
+                    gradients.scaled_add_assign(error, &batch.x.row(i))?;
+                    m += 1;
+                }
+                theta.scaled_add_assign(settings.learning_rate.neg() / batch_len, &gradients)?;
+            }
+            if m == 0 {
+                return Err(LMSError::EmptyDataset);
+            }
+            let cost_change = num::Float::abs(previous_cost - cost);
+            let cost_change = cost_change / T::from(m).ok_or(LMSError::FailedCastToT)?;
+            if cost_change < settings.eps {
+                break;
+            }
+            previous_cost = cost;
+            if iteration_count >= settings.max_iteration_count {
+                break;
+            }
+            iteration_count += 1;
+        }
+        Ok(LMSResult {
+            theta,
+            iteration_count,
+        })
+    }
+}
@@ -1,12 +1,15 @@
 mod batch_kernel;
 mod kernel;
+mod mini_batch_kernel;
 mod stochastic_kernel;
 
+use crate::data_source::DataSource;
 use crate::lms::kernel::LMSSettingsFilled;
-use num_traits;
 
 pub use crate::lms::batch_kernel::BatchKernel;
 pub use crate::lms::kernel::Kernel;
+pub use crate::lms::kernel::StreamingKernel;
+pub use crate::lms::mini_batch_kernel::MiniBatchKernel;
 pub use crate::lms::stochastic_kernel::StochasticKernel;
 
 pub use crate::lms::kernel::LMSError;
@@ -19,6 +22,7 @@ pub struct LMSSettings<T> {
     pub learning_rate: Option<T>,
     pub eps: Option<T>,
     pub starting_theta: Option<array::Array1<T>>,
+    pub batch_size: Option<usize>,
 }
 
 fn fill_missing_settings<T>(
@@ -35,19 +39,24 @@ where
             learning_rate: Some(T::from(0.001).ok_or(LMSError::FailedCastToT)?),
             eps: Some(T::from(0.00001).ok_or(LMSError::FailedCastToT)?),
             starting_theta: None,
+            batch_size: None,
         },
     };
+    if settings.batch_size == Some(0) {
+        return Err(LMSError::InvalidBatchSize);
+    }
     Ok(LMSSettingsFilled::<T> {
         max_iteration_count: settings.max_iteration_count.unwrap_or(10000),
         learning_rate: settings
             .learning_rate
             .unwrap_or(T::from(0.001).ok_or(LMSError::FailedCastToT)?),
         eps: settings
-            .learning_rate
+            .eps
             .unwrap_or(T::from(0.00001).ok_or(LMSError::FailedCastToT)?),
         starting_theta: settings
             .starting_theta
             .unwrap_or(array::Array1::zeros(theta_dimensions)),
+        batch_size: settings.batch_size.unwrap_or(32),
     })
 }
 
@@ -60,8 +69,27 @@ pub fn lms_solve<T, K, F>(
 where
     T: num_traits::Float + num_traits::NumAssignOps,
     K: kernel::Kernel<T>,
-    F: Fn(array::ArrayView1<T>) -> T,
+    F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
 {
+    if x.nrows() == 0 {
+        return Err(LMSError::EmptyDataset);
+    }
     let settings = fill_missing_settings(settings, x.ncols())?;
     K::compute(x, y, settings, weight_function)
 }
+
+// trains on the samples of source, which is rewound before every pass over it
+pub fn lms_solve_streaming<T, K, D, F>(
+    source: &mut D,
+    settings: Option<LMSSettings<T>>,
+    weight_function: F,
+) -> Result<LMSResult<T>, LMSError>
+where
+    T: num_traits::Float + num_traits::NumAssignOps,
+    K: kernel::StreamingKernel<T>,
+    D: DataSource<T>,
+    F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
+{
+    let settings = fill_missing_settings(settings, source.feature_names().len())?;
+    K::compute_streaming(source, settings, weight_function)
+}
@@ -1,5 +1,6 @@
 use crate::array;
-use crate::linalg::arithmetic::Arithmetic;
+use crate::data_source::{ArraySource, DataSource};
+use crate::linalg::arithmetic::ArithmeticAssign;
 use crate::linalg::dot::Dot;
 use crate::lms::kernel::*;
 
@@ -16,19 +17,50 @@ where
         weight_function: F,
     ) -> Result<LMSResult<T>, LMSError>
     where
-        F: Fn(array::ArrayView1<T>) -> T,
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
+    {
+        Self::compute_streaming(
+            &mut ArraySource::new(x.slice(.., ..), y.slice(..)),
+            settings,
+            weight_function,
+        )
+    }
+}
+
+impl<T> StreamingKernel<T> for StochasticKernel
+where
+    T: num_traits::Float + num_traits::NumAssignOps + std::iter::Sum,
+{
+    fn compute_streaming<D, F>(
+        source: &mut D,
+        settings: LMSSettingsFilled<T>,
+        weight_function: F,
+    ) -> Result<LMSResult<T>, LMSError>
+    where
+        D: DataSource<T>,
+        F: Fn(array::ArrayView1<T>) -> Result<T, LMSError>,
     {
-        let m = x.nrows();
         let mut iteration_count = 0;
         let mut previous_cost = T::zero();
         let mut theta = settings.starting_theta.clone();
         loop {
+            source.rewind().map_err(LMSError::data_source)?;
+            let mut m = 0;
             let mut cost = T::zero();
-            for i in 0..m {
-                let weight = weight_function(x.row(i));
-                let error = weight * (x.row(i).dot(&theta) - y[i]);
-                cost += error * error;
-                theta = theta.scaled_add((settings.learning_rate * error).neg(), &x.row(i));
+            while let Some(batch) = source.next_batch(1).map_err(LMSError::data_source)? {
+                for i in 0..batch.x.nrows() {
+                    let weight = weight_function(batch.x.row(i))?;
+                    let error = weight * (batch.x.row(i).dot(&theta)? - batch.y[i]);
+                    cost += error * error;
+                    theta.scaled_add_assign(
+                        (settings.learning_rate * error).neg(),
+                        &batch.x.row(i),
+                    )?;
+                    m += 1;
+                }
+            }
+            if m == 0 {
+                return Err(LMSError::EmptyDataset);
             }
             let cost_change = num::Float::abs(previous_cost - cost);
             let cost_change = cost_change / T::from(m).ok_or(LMSError::FailedCastToT)?;
@@ -39,6 +39,7 @@ where
             learning_rate: Some(self.settings.common_settings.learning_rate),
             eps: Some(self.settings.common_settings.eps),
             starting_theta: Some(self.settings.common_settings.starting_theta.to_owned()),
+            batch_size: Some(self.settings.common_settings.batch_size),
         };
         let lms_result = lms::lms_solve::<T, lms::BatchKernel, _>(
             self.training_data.x.view(),
@@ -46,14 +47,14 @@ where
             Some(settings),
             |x| {
                 // L2||x_i - x||
-                let diff = x_i.sub(&x);
-                let distance: T = diff.dot(&diff);
+                let diff = x_i.sub(&x)?;
+                let distance: T = diff.dot(&diff)?;
                 let two = T::one() + T::one();
                 // e^{-\frac{distance^2}{2*\tau^2}}
-                T::E().powf(distance / two * self.settings.bandwith.powi(2).neg())
+                Ok(T::E().powf(distance / two * self.settings.bandwith.powi(2).neg()))
             },
         )?;
-        let result = x_i.dot(&lms_result.theta);
+        let result = x_i.dot(&lms_result.theta)?;
         Ok(result)
     }
 }
@@ -1,7 +1,10 @@
 use crate::array;
 use crate::array::Transpose;
 use crate::linalg::Dot;
+use crate::linalg::LinalgError;
 use crate::linalg::LLT;
+use crate::linalg::QR;
+use crate::linalg::SVD;
 use crate::linear_regression;
 use crate::linear_regression::LinearRegressionError;
 use crate::parametric_algorithm;
@@ -9,6 +12,20 @@ use crate::training_data;
 
 pub struct NormalEquation<T> {
     theta: array::Array1<T>,
+    feature_names: Vec<String>,
+    solver: Solver,
+    rank: usize,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Solver {
+    // Cholesky factorization of XᵀX, fast but squares the condition number of X
+    Cholesky,
+    // Householder QR of X itself, for badly conditioned design matrices
+    QR,
+    // θ = X⁺y through the SVD of X, also used as a fallback when X is rank deficient
+    PseudoInverse,
 }
 
 impl<T> linear_regression::LinearRegressionModel<T> for NormalEquation<T>
@@ -16,7 +33,14 @@ where
     T: num_traits::Float + num_traits::NumAssign,
 {
     fn predict(&self, x: &array::ArrayView1<T>) -> Result<T, LinearRegressionError> {
-        Ok(x.dot(&self.theta))
+        Ok(x.dot(&self.theta)?)
+    }
+
+    fn predict_batch(
+        &self,
+        x: &array::ArrayView2<T>,
+    ) -> Result<array::Array1<T>, LinearRegressionError> {
+        Ok(x.dot(&self.theta)?)
     }
 }
 
@@ -24,30 +48,75 @@ impl<T> parametric_algorithm::ParametricAlgorithm<T> for NormalEquation<T>
 where
     T: num_traits::Float + num_traits::NumAssign,
 {
-    fn theta(&self) -> array::ArrayView1<T> {
+    fn theta(&self) -> array::ArrayView1<'_, T> {
         self.theta.view()
     }
 }
 
+impl<T> parametric_algorithm::FeatureNames for NormalEquation<T> {
+    fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+}
+
 impl<T> NormalEquation<T>
 where
-    T: num_traits::Float + std::iter::Sum,
+    T: num_traits::Float + std::iter::Sum + Send + Sync,
 {
     pub fn new(
         training_data: &training_data::TrainingData<T>,
+    ) -> Result<Self, LinearRegressionError> {
+        Self::with_solver(training_data, Solver::Cholesky)
+    }
+
+    pub fn with_solver(
+        training_data: &training_data::TrainingData<T>,
+        solver: Solver,
+    ) -> Result<Self, LinearRegressionError> {
+        match Self::solve(training_data, solver) {
+            Err(LinearRegressionError::LinalgError(
+                LinalgError::NotPositiveDefinite { .. } | LinalgError::Singular { .. },
+            )) => Self::solve(training_data, Solver::PseudoInverse),
+            result => result,
+        }
+    }
+
+    // Solver actually used, differs from the requested one after a fallback
+    pub fn solver(&self) -> Solver {
+        self.solver
+    }
+
+    // Effective rank of X used to compute θ
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    fn solve(
+        training_data: &training_data::TrainingData<T>,
+        solver: Solver,
     ) -> Result<Self, LinearRegressionError> {
         let x = training_data.x.view();
-        let x_t = x.t();
-        let y_as_matrix = training_data
-            .y
-            .clone()
-            .into_shape((training_data.y.len(), 1))?;
-        // θ = (XᵀX)⁻¹Xᵀy
-        let theta = x_t.dot(&x).llt().inv().dot(&x_t).dot(&y_as_matrix);
-
-        let theta_len = theta.len();
+        let mut rank = x.ncols();
+        let theta = match solver {
+            Solver::Cholesky => {
+                let x_t = x.t();
+                // XᵀXθ = Xᵀy
+                x_t.dot(&x)?.llt()?.solve(&x_t.dot(&training_data.y)?)?
+            }
+            // Rθ = Qᵀy
+            Solver::QR => x.qr().solve(&training_data.y)?,
+            Solver::PseudoInverse => {
+                let svd = x.svd()?;
+                rank = svd.rank();
+                svd.solve(&training_data.y)?
+            }
+        };
+
         Ok(Self {
-            theta: theta.into_shape(theta_len)?,
+            theta,
+            feature_names: training_data.feature_names.clone(),
+            solver,
+            rank,
         })
     }
 }
@@ -57,3 +126,73 @@ impl From<array::ShapeError> for LinearRegressionError {
         LinearRegressionError::OperationFailed
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{NormalEquation, Solver};
+    use crate::array;
+    use crate::parametric_algorithm::ParametricAlgorithm;
+    use crate::training_data::{TrainingData, INTERCEPT};
+
+    #[test]
+    fn test_solvers_agree() {
+        let training_data = TrainingData::new(
+            array![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0], [1.0, 3.0]],
+            array![1.5, 2.5, 5.5, 6.5],
+        );
+        for solver in [Solver::Cholesky, Solver::QR, Solver::PseudoInverse] {
+            let model = NormalEquation::<f64>::with_solver(&training_data, solver).unwrap();
+            assert_eq!(model.solver(), solver);
+            assert_eq!(model.rank(), 2);
+            assert!((model.theta()[0] - 1.3).abs() < 1e-12);
+            assert!((model.theta()[1] - 1.8).abs() < 1e-12);
+            assert_eq!(
+                model.named_theta(),
+                vec![(INTERCEPT, model.theta()[0]), ("x_1", model.theta()[1])]
+            );
+        }
+    }
+
+    #[test]
+    fn test_rank_deficient_fallback() {
+        // the last feature duplicates the second one
+        let training_data = TrainingData::new(
+            array![
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 1.0],
+                [1.0, 2.0, 2.0],
+                [1.0, 3.0, 3.0]
+            ],
+            array![1.5, 2.5, 5.5, 6.5],
+        );
+        for solver in [Solver::Cholesky, Solver::QR] {
+            let model = NormalEquation::<f64>::with_solver(&training_data, solver).unwrap();
+            assert_eq!(model.solver(), Solver::PseudoInverse);
+            assert_eq!(model.rank(), 2);
+            // minimum norm solution splits the slope evenly between the duplicates
+            assert!((model.theta()[0] - 1.3).abs() < 1e-12);
+            assert!((model.theta()[1] - 0.9).abs() < 1e-12);
+            assert!((model.theta()[2] - 0.9).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_near_collinear_fallback() {
+        // the last feature is the second one plus noise far below the
+        // precision of XᵀX, so Cholesky only sees rounding errors
+        let noise = [0.3e-8, -0.1e-8, 0.25e-8, -0.45e-8];
+        let a = [0.1, 1.3, 2.2, 3.7];
+        let mut x = array::Array2::<f64>::zeros((4, 3));
+        for i in 0..4 {
+            x[(i, 0)] = 1.0;
+            x[(i, 1)] = a[i];
+            x[(i, 2)] = a[i] + noise[i];
+        }
+        let training_data = TrainingData::new(x, array![1.5, 2.5, 5.5, 6.5]);
+        let model = NormalEquation::<f64>::new(&training_data).unwrap();
+        assert_eq!(model.solver(), Solver::PseudoInverse);
+        let pseudo_inverse =
+            NormalEquation::<f64>::with_solver(&training_data, Solver::PseudoInverse).unwrap();
+        assert_eq!(model.theta(), pseudo_inverse.theta());
+    }
+}
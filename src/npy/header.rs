@@ -0,0 +1,310 @@
+use crate::npy::NpyError;
+
+use std::io::{Read, Write};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+// numpy pads the header so that the data starts at a multiple of this
+const ALIGNMENT: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    pub fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            ByteOrder::BigEndian
+        } else {
+            ByteOrder::LittleEndian
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header {
+    // type code without the byte order, e.g. "f8"
+    pub dtype: String,
+    pub byte_order: ByteOrder,
+    pub fortran_order: bool,
+    pub shape: Vec<usize>,
+}
+
+impl Header {
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, NpyError> {
+        let mut preamble = [0u8; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != MAGIC {
+            return Err(NpyError::InvalidMagic);
+        }
+        let (major, minor) = (preamble[6], preamble[7]);
+        let header_len = match major {
+            1 => {
+                let mut len = [0u8; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            _ => return Err(NpyError::UnsupportedVersion { major, minor }),
+        };
+        let mut text = Vec::new();
+        reader.take(header_len as u64).read_to_end(&mut text)?;
+        if text.len() != header_len {
+            return Err(NpyError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        // version 3 only differs from 2 by allowing utf8 in the header
+        let text = String::from_utf8(text)
+            .map_err(|_| NpyError::InvalidHeader("header is not valid text".to_string()))?;
+        Header::parse(&text)
+    }
+
+    // size of the data in bytes, the shape comes from the file and is not trusted
+    pub fn data_len(&self, element_size: usize) -> Result<usize, NpyError> {
+        self.shape
+            .iter()
+            .try_fold(element_size, |len, &dimension| len.checked_mul(dimension))
+            .ok_or_else(|| NpyError::InvalidHeader("shape is too large".to_string()))
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), NpyError> {
+        let order = match self.byte_order {
+            ByteOrder::LittleEndian => '<',
+            ByteOrder::BigEndian => '>',
+        };
+        let fortran_order = if self.fortran_order { "True" } else { "False" };
+        // a one dimensional shape needs the trailing comma to be a tuple
+        let shape = match self.shape.as_slice() {
+            [length] => format!("({},)", length),
+            shape => format!(
+                "({})",
+                shape
+                    .iter()
+                    .map(|length| length.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut text = format!(
+            "{{'descr': '{}{}', 'fortran_order': {}, 'shape': {}, }}",
+            order, self.dtype, fortran_order, shape
+        );
+
+        // magic, version and the header length take 10 bytes in version 1
+        // and 12 in version 2, the text is padded with spaces and ends in a newline
+        let mut preamble_len = MAGIC.len() + 2 + 2;
+        if text.len() + 1 + ALIGNMENT > u16::MAX as usize {
+            preamble_len += 2;
+        }
+        let padding = (ALIGNMENT - (preamble_len + text.len() + 1) % ALIGNMENT) % ALIGNMENT;
+        text.extend(std::iter::repeat_n(' ', padding));
+        text.push('\n');
+
+        writer.write_all(MAGIC)?;
+        if preamble_len == MAGIC.len() + 4 {
+            writer.write_all(&[1, 0])?;
+            writer.write_all(&(text.len() as u16).to_le_bytes())?;
+        } else {
+            writer.write_all(&[2, 0])?;
+            writer.write_all(&(text.len() as u32).to_le_bytes())?;
+        }
+        writer.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    // the header is the repr of a python dict with exactly the keys
+    // descr, fortran_order and shape
+    fn parse(text: &str) -> Result<Self, NpyError> {
+        let mut parser = Parser {
+            text: text.trim_end(),
+            position: 0,
+        };
+        let mut descr = None;
+        let mut fortran_order = None;
+        let mut shape = None;
+
+        parser.expect('{')?;
+        while !parser.eat('}') {
+            let key = parser.string()?;
+            parser.expect(':')?;
+            match key.as_str() {
+                "descr" => descr = Some(parser.string()?),
+                "fortran_order" => fortran_order = Some(parser.boolean()?),
+                "shape" => shape = Some(parser.tuple()?),
+                _ => return Err(NpyError::InvalidHeader(format!("unknown key '{}'", key))),
+            }
+            if !parser.eat(',') {
+                parser.expect('}')?;
+                break;
+            }
+        }
+        parser.end()?;
+
+        let descr = descr.ok_or_else(|| NpyError::InvalidHeader("missing descr".to_string()))?;
+        let (byte_order, dtype) = match descr.chars().next() {
+            Some('<') => (ByteOrder::LittleEndian, &descr[1..]),
+            Some('>') => (ByteOrder::BigEndian, &descr[1..]),
+            Some('=') | Some('|') => (ByteOrder::native(), &descr[1..]),
+            _ => (ByteOrder::native(), descr.as_str()),
+        };
+        Ok(Header {
+            dtype: dtype.to_string(),
+            byte_order,
+            fortran_order: fortran_order
+                .ok_or_else(|| NpyError::InvalidHeader("missing fortran_order".to_string()))?,
+            shape: shape.ok_or_else(|| NpyError::InvalidHeader("missing shape".to_string()))?,
+        })
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), NpyError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", c)))
+        }
+    }
+
+    fn end(&mut self) -> Result<(), NpyError> {
+        self.skip_whitespace();
+        if self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of header"))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> NpyError {
+        NpyError::InvalidHeader(format!(
+            "expected {} at offset {} of '{}'",
+            expected, self.position, self.text
+        ))
+    }
+
+    fn string(&mut self) -> Result<String, NpyError> {
+        self.skip_whitespace();
+        let quote = match self.rest().chars().next() {
+            Some(quote @ ('\'' | '"')) => quote,
+            _ => return Err(self.unexpected("a string")),
+        };
+        let start = self.position + 1;
+        match self.text[start..].find(quote) {
+            Some(length) => {
+                self.position = start + length + 1;
+                Ok(self.text[start..start + length].to_string())
+            }
+            None => Err(self.unexpected("a closing quote")),
+        }
+    }
+
+    fn boolean(&mut self) -> Result<bool, NpyError> {
+        self.skip_whitespace();
+        for (word, value) in [("True", true), ("False", false)] {
+            if self.rest().starts_with(word) {
+                self.position += word.len();
+                return Ok(value);
+            }
+        }
+        Err(self.unexpected("True or False"))
+    }
+
+    fn integer(&mut self) -> Result<usize, NpyError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        // python 2 era files may write lengths as longs, e.g. 3L
+        let value = rest[..digits]
+            .parse()
+            .map_err(|_| self.unexpected("a length"))?;
+        self.position += digits;
+        if self.rest().starts_with('L') {
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    fn tuple(&mut self) -> Result<Vec<usize>, NpyError> {
+        self.expect('(')?;
+        let mut values = Vec::new();
+        while !self.eat(')') {
+            values.push(self.integer()?);
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        for shape in [vec![], vec![3], vec![2, 70000]] {
+            let header = Header {
+                dtype: "f8".to_string(),
+                byte_order: ByteOrder::LittleEndian,
+                fortran_order: false,
+                shape,
+            };
+            let mut bytes = Vec::new();
+            header.write(&mut bytes).unwrap();
+            assert_eq!(bytes.len() % ALIGNMENT, 0);
+            assert_eq!(*bytes.last().unwrap(), b'\n');
+            assert_eq!(Header::read(&mut bytes.as_slice()).unwrap(), header);
+        }
+    }
+
+    #[test]
+    fn test_parse_python_headers() {
+        let header =
+            Header::parse("{'descr': '>f4', 'fortran_order': True, 'shape': (2L, 3L), }   \n")
+                .unwrap();
+        assert_eq!(header.dtype, "f4");
+        assert_eq!(header.byte_order, ByteOrder::BigEndian);
+        assert!(header.fortran_order);
+        assert_eq!(header.shape, vec![2, 3]);
+
+        let header =
+            Header::parse("{\"shape\":(4,),\"fortran_order\":False,\"descr\":\"<f8\"}").unwrap();
+        assert_eq!(header.shape, vec![4]);
+
+        assert!(Header::parse("{'descr': '<f8', 'fortran_order': False}").is_err());
+        assert!(Header::parse("{'descr': '<f8', 'fortran_order': 0, 'shape': ()}").is_err());
+        assert!(
+            Header::parse("{'descr': '<f8', 'fortran_order': False, 'shape': (2,)} x").is_err()
+        );
+    }
+}
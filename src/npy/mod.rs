@@ -0,0 +1,398 @@
+pub mod header;
+pub mod npz;
+
+pub use crate::npy::header::{ByteOrder, Header};
+pub use crate::npy::npz::{Compression, NpzReader, NpzWriter};
+
+use crate::array::{Array, ArrayBase, ShapeError};
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum NpyError {
+    Io(std::io::Error),
+    InvalidMagic,
+    UnsupportedVersion {
+        major: u8,
+        minor: u8,
+    },
+    InvalidHeader(String),
+    // dtype of the file without byte order, e.g. "f4" when "f8" was requested
+    DtypeMismatch {
+        expected: String,
+        found: String,
+    },
+    // data that would need byte swapping cannot be memory mapped
+    ByteOrderMismatch {
+        expected: ByteOrder,
+        found: ByteOrder,
+    },
+    ShapeError(ShapeError),
+    InvalidArchive(String),
+    MissingEntry(String),
+    ChecksumMismatch(String),
+}
+
+impl From<std::io::Error> for NpyError {
+    fn from(error: std::io::Error) -> Self {
+        NpyError::Io(error)
+    }
+}
+
+impl From<ShapeError> for NpyError {
+    fn from(error: ShapeError) -> Self {
+        NpyError::ShapeError(error)
+    }
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Io(error) => write!(f, "{}", error),
+            NpyError::InvalidMagic => write!(f, "not a .npy file"),
+            NpyError::UnsupportedVersion { major, minor } => {
+                write!(f, "unsupported .npy version {}.{}", major, minor)
+            }
+            NpyError::InvalidHeader(message) => write!(f, "invalid header: {}", message),
+            NpyError::DtypeMismatch { expected, found } => {
+                write!(f, "expected dtype {}, found {}", expected, found)
+            }
+            NpyError::ByteOrderMismatch { expected, found } => {
+                write!(f, "expected {:?} data, found {:?}", expected, found)
+            }
+            NpyError::ShapeError(error) => write!(f, "{}", error),
+            NpyError::InvalidArchive(message) => write!(f, "invalid .npz archive: {}", message),
+            NpyError::MissingEntry(name) => write!(f, "no array named '{}'", name),
+            NpyError::ChecksumMismatch(name) => write!(f, "checksum mismatch for '{}'", name),
+        }
+    }
+}
+
+impl Error for NpyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NpyError::Io(error) => Some(error),
+            NpyError::ShapeError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+// element types that have a fixed numpy dtype. Sealed because MappedArray
+// reinterprets file bytes as elements, which is only sound for types where
+// every bit pattern is a valid value
+pub trait NpyElement: Copy + sealed::Sealed {
+    const DTYPE: &'static str;
+    const SIZE: usize;
+
+    fn from_bytes(bytes: &[u8], byte_order: ByteOrder) -> Self;
+    fn to_bytes(self, byte_order: ByteOrder) -> Vec<u8>;
+}
+
+macro_rules! impl_npy_element {
+    ($t:ty, $dtype:expr) => {
+        impl NpyElement for $t {
+            const DTYPE: &'static str = $dtype;
+            const SIZE: usize = std::mem::size_of::<$t>();
+
+            fn from_bytes(bytes: &[u8], byte_order: ByteOrder) -> Self {
+                let bytes = bytes.try_into().unwrap();
+                match byte_order {
+                    ByteOrder::LittleEndian => <$t>::from_le_bytes(bytes),
+                    ByteOrder::BigEndian => <$t>::from_be_bytes(bytes),
+                }
+            }
+
+            fn to_bytes(self, byte_order: ByteOrder) -> Vec<u8> {
+                match byte_order {
+                    ByteOrder::LittleEndian => <$t>::to_le_bytes(self).to_vec(),
+                    ByteOrder::BigEndian => <$t>::to_be_bytes(self).to_vec(),
+                }
+            }
+        }
+    };
+}
+
+impl_npy_element!(f32, "f4");
+impl_npy_element!(f64, "f8");
+
+pub fn read_npy<A, D, P>(path: P) -> Result<Array<A, D>, NpyError>
+where
+    A: NpyElement,
+    D: ndarray::Dimension,
+    P: AsRef<Path>,
+{
+    read_npy_from(BufReader::new(File::open(path)?))
+}
+
+// the element type and the number of dimensions must match the file exactly
+pub fn read_npy_from<A, D, R>(mut reader: R) -> Result<Array<A, D>, NpyError>
+where
+    A: NpyElement,
+    D: ndarray::Dimension,
+    R: Read,
+{
+    let header = Header::read(&mut reader)?;
+    if header.dtype != A::DTYPE {
+        return Err(NpyError::DtypeMismatch {
+            expected: A::DTYPE.to_string(),
+            found: header.dtype,
+        });
+    }
+    let len = header.data_len(A::SIZE)?;
+    // grows with the data actually read, a header claiming a huge shape does not allocate it up front
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(NpyError::Io(std::io::ErrorKind::UnexpectedEof.into()));
+    }
+    let data = bytes
+        .chunks_exact(A::SIZE)
+        .map(|chunk| A::from_bytes(chunk, header.byte_order))
+        .collect();
+
+    let shape = ndarray::IxDyn(&header.shape);
+    let array = if header.fortran_order {
+        // keep the rest of the crate on row major data
+        let array = ndarray::Array::from_shape_vec(ndarray::ShapeBuilder::f(shape), data)?;
+        array.as_standard_layout().into_owned()
+    } else {
+        ndarray::Array::from_shape_vec(shape, data)?
+    };
+    Ok(ArrayBase::new(array.into_dimensionality::<D>()?))
+}
+
+// how write_npy_with lays out the data, numpy reads every combination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WriteOptions {
+    pub byte_order: ByteOrder,
+    // column major instead of row major order
+    pub fortran_order: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            byte_order: ByteOrder::LittleEndian,
+            fortran_order: false,
+        }
+    }
+}
+
+pub fn write_npy<A, S, D, P>(path: P, array: &ArrayBase<S, D>) -> Result<(), NpyError>
+where
+    A: NpyElement,
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+    P: AsRef<Path>,
+{
+    write_npy_with(path, array, &WriteOptions::default())
+}
+
+pub fn write_npy_with<A, S, D, P>(
+    path: P,
+    array: &ArrayBase<S, D>,
+    options: &WriteOptions,
+) -> Result<(), NpyError>
+where
+    A: NpyElement,
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+    P: AsRef<Path>,
+{
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy_to_with(&mut writer, array, options)?;
+    writer.flush()?;
+    Ok(())
+}
+
+// little endian data in C order, whatever the memory layout of array
+pub fn write_npy_to<A, S, D, W>(writer: W, array: &ArrayBase<S, D>) -> Result<(), NpyError>
+where
+    A: NpyElement,
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+    W: Write,
+{
+    write_npy_to_with(writer, array, &WriteOptions::default())
+}
+
+pub fn write_npy_to_with<A, S, D, W>(
+    mut writer: W,
+    array: &ArrayBase<S, D>,
+    options: &WriteOptions,
+) -> Result<(), NpyError>
+where
+    A: NpyElement,
+    S: ndarray::Data<Elem = A>,
+    D: ndarray::Dimension,
+    W: Write,
+{
+    let header = Header {
+        dtype: A::DTYPE.to_string(),
+        byte_order: options.byte_order,
+        fortran_order: options.fortran_order,
+        shape: array.shape().to_vec(),
+    };
+    header.write(&mut writer)?;
+    if options.fortran_order {
+        // the logical order of the reversed axes is column major order
+        for &value in array.inner_impl().t() {
+            writer.write_all(&value.to_bytes(options.byte_order))?;
+        }
+    } else {
+        for &value in array {
+            writer.write_all(&value.to_bytes(options.byte_order))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::array::{Array1, Array2, Ix2};
+
+    fn npy_bytes(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+        let mut text = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            descr,
+            if fortran_order { "True" } else { "False" },
+            shape
+        );
+        while (10 + text.len() + 1) % 64 != 0 {
+            text.push(' ');
+        }
+        text.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((text.len() as u16).to_le_bytes());
+        bytes.extend(text.as_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let x = array![[1.0, -2.5, 3.0], [4.0, 5.0, f64::MAX]];
+        let mut bytes = Vec::new();
+        write_npy_to(&mut bytes, &x).unwrap();
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        assert_eq!(bytes.len(), 128 + 6 * 8);
+        let y: Array2<f64> = read_npy_from(bytes.as_slice()).unwrap();
+        assert_eq!(x, y);
+
+        // a transposed view is written in its logical order
+        let mut bytes = Vec::new();
+        write_npy_to(&mut bytes, &crate::array::Transpose::t(&x)).unwrap();
+        let y: Array2<f64> = read_npy_from(bytes.as_slice()).unwrap();
+        assert_eq!(y, array![[1.0, 4.0], [-2.5, 5.0], [3.0, f64::MAX]]);
+
+        let theta = array![0.5f32, -1.0];
+        let mut bytes = Vec::new();
+        write_npy_to(&mut bytes, &theta).unwrap();
+        let read: Array1<f32> = read_npy_from(bytes.as_slice()).unwrap();
+        assert_eq!(theta, read);
+    }
+
+    #[test]
+    fn test_write_byte_order_and_layout() {
+        let x = array![[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        for byte_order in [ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+            for fortran_order in [false, true] {
+                let options = WriteOptions {
+                    byte_order,
+                    fortran_order,
+                };
+                let mut bytes = Vec::new();
+                write_npy_to_with(&mut bytes, &x, &options).unwrap();
+                let header = Header::read(&mut bytes.as_slice()).unwrap();
+                assert_eq!(header.byte_order, byte_order);
+                assert_eq!(header.fortran_order, fortran_order);
+                let y: Array2<f64> = read_npy_from(bytes.as_slice()).unwrap();
+                assert_eq!(x, y);
+            }
+        }
+
+        // the same bytes numpy writes for np.asfortranarray(x).astype('>f4')
+        let options = WriteOptions {
+            byte_order: ByteOrder::BigEndian,
+            fortran_order: true,
+        };
+        let mut bytes = Vec::new();
+        write_npy_to_with(&mut bytes, &array![[1.0f32, 2.0], [3.0, 4.0]], &options).unwrap();
+        let data: Vec<u8> = [1.0f32, 3.0, 2.0, 4.0]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect();
+        assert_eq!(bytes, npy_bytes(">f4", true, "(2, 2)", &data));
+    }
+
+    #[test]
+    fn test_read_byte_order_and_layout() {
+        let data: Vec<u8> = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect();
+        let bytes = npy_bytes(">f4", false, "(2, 3)", &data);
+        let x: Array2<f32> = read_npy_from(bytes.as_slice()).unwrap();
+        assert_eq!(x, array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+        // column major data, each column is stored contiguously
+        let data: Vec<u8> = [1.0f64, 4.0, 2.0, 5.0, 3.0, 6.0]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        let bytes = npy_bytes("<f8", true, "(2, 3)", &data);
+        let x: Array2<f64> = read_npy_from(bytes.as_slice()).unwrap();
+        assert_eq!(x, array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let data: Vec<u8> = [1.0f64, 2.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+        let bytes = npy_bytes("<f8", false, "(2,)", &data);
+        assert!(matches!(
+            read_npy_from::<f32, Ix2, _>(bytes.as_slice()),
+            Err(NpyError::DtypeMismatch { .. })
+        ));
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(bytes.as_slice()),
+            Err(NpyError::ShapeError(_))
+        ));
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(&bytes[..bytes.len() - 1]),
+            Err(NpyError::Io(_))
+        ));
+        // a shape whose size overflows usize
+        let bytes = npy_bytes("<f8", false, "(4294967296, 4294967296)", &data);
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(bytes.as_slice()),
+            Err(NpyError::InvalidHeader(_))
+        ));
+        let bytes = npy_bytes("<f8", false, "(1000000000000,)", &data);
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(bytes.as_slice()),
+            Err(NpyError::Io(_))
+        ));
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(&b"PK\x03\x04"[..]),
+            Err(NpyError::Io(_))
+        ));
+        assert!(matches!(
+            read_npy_from::<f64, Ix2, _>(&b"\x93NUMPX\x01\x00\x00\x00"[..]),
+            Err(NpyError::InvalidMagic)
+        ));
+    }
+}
@@ -0,0 +1,464 @@
+use crate::array::{Array, ArrayBase};
+use crate::npy::{read_npy_from, write_npy_to, NpyElement, NpyError};
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+// .npz files are zip archives holding one .npy file per array, as written by
+// numpy.savez (stored) and numpy.savez_compressed (deflate)
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06064b50;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const ZIP64_EXTRA_FIELD: u16 = 0x0001;
+
+const LOCAL_HEADER_LEN: usize = 30;
+const CENTRAL_HEADER_LEN: usize = 46;
+const END_OF_CENTRAL_DIRECTORY_LEN: usize = 22;
+const ZIP64_LOCATOR_LEN: usize = 20;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+// 1980-01-01 00:00, the earliest time a zip archive can record
+const DOS_DATE: u16 = (1 << 5) | 1;
+const VERSION: u16 = 20;
+// bit 11 marks the file names as utf8
+const FLAG_UTF8: u16 = 1 << 11;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Stored,
+    Deflated,
+}
+
+impl Compression {
+    fn method(self) -> u16 {
+        match self {
+            Compression::Stored => METHOD_STORED,
+            Compression::Deflated => METHOD_DEFLATED,
+        }
+    }
+}
+
+fn invalid(message: &str) -> NpyError {
+    NpyError::InvalidArchive(message.to_string())
+}
+
+fn npy_name(name: &str) -> String {
+    if name.ends_with(".npy") {
+        name.to_string()
+    } else {
+        format!("{}.npy", name)
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+// zip64 sizes and offsets are u64, which can exceed the address space of
+// 32 bit targets
+fn to_usize(value: u64) -> Result<usize, NpyError> {
+    usize::try_from(value).map_err(|_| invalid("size or offset does not fit in memory"))
+}
+
+// little endian field access that reports truncated archives instead of panicking
+struct Fields<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn at(bytes: &'a [u8], position: usize) -> Self {
+        Fields { bytes, position }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], NpyError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| invalid("unexpected end of archive"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, NpyError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, NpyError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, NpyError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    local_header_offset: u64,
+}
+
+pub struct NpzReader {
+    bytes: Vec<u8>,
+    entries: Vec<Entry>,
+}
+
+impl NpzReader {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        NpzReader::from_reader(File::open(path)?)
+    }
+
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, NpyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let entries = read_central_directory(&bytes)?;
+        Ok(NpzReader { bytes, entries })
+    }
+
+    // array names in archive order, without the .npy extension
+    pub fn names(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.name.strip_suffix(".npy").unwrap_or(&entry.name))
+            .collect()
+    }
+
+    pub fn by_name<A, D>(&self, name: &str) -> Result<Array<A, D>, NpyError>
+    where
+        A: NpyElement,
+        D: ndarray::Dimension,
+    {
+        let file_name = npy_name(name);
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == file_name)
+            .ok_or_else(|| NpyError::MissingEntry(name.to_string()))?;
+
+        let mut fields = Fields::at(&self.bytes, to_usize(entry.local_header_offset)?);
+        if fields.u32()? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid("bad local file header"));
+        }
+        // the sizes in the local header may be placeholders, the central
+        // directory is authoritative so only the name and extra lengths are used
+        fields.take(LOCAL_HEADER_LEN - 8)?;
+        let name_len = fields.u16()? as usize;
+        let extra_len = fields.u16()? as usize;
+        fields.take(name_len + extra_len)?;
+        let data = fields.take(to_usize(entry.compressed_size)?)?;
+
+        let contents = match entry.method {
+            METHOD_STORED => data.to_vec(),
+            METHOD_DEFLATED => {
+                // the sizes come from the archive, so nothing is allocated up front and
+                // decompression stops one byte past the claimed size
+                let mut contents = Vec::new();
+                flate2::read::DeflateDecoder::new(data)
+                    .take(entry.uncompressed_size.saturating_add(1))
+                    .read_to_end(&mut contents)?;
+                contents
+            }
+            method => {
+                return Err(NpyError::InvalidArchive(format!(
+                    "unsupported compression method {}",
+                    method
+                )))
+            }
+        };
+        if contents.len() as u64 != entry.uncompressed_size || crc32(&contents) != entry.crc {
+            return Err(NpyError::ChecksumMismatch(name.to_string()));
+        }
+        read_npy_from(contents.as_slice())
+    }
+}
+
+fn read_central_directory(bytes: &[u8]) -> Result<Vec<Entry>, NpyError> {
+    if bytes.len() < END_OF_CENTRAL_DIRECTORY_LEN {
+        return Err(invalid("missing end of central directory"));
+    }
+    // the end of central directory record is followed only by a comment of
+    // at most u16::MAX bytes, so scan backwards for its signature
+    let search_start = bytes
+        .len()
+        .saturating_sub(END_OF_CENTRAL_DIRECTORY_LEN + u16::MAX as usize);
+    let end = (search_start..=bytes.len() - END_OF_CENTRAL_DIRECTORY_LEN)
+        .rev()
+        .find(|&i| bytes[i..i + 4] == END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes())
+        .ok_or_else(|| invalid("missing end of central directory"))?;
+
+    let mut fields = Fields::at(bytes, end + 10);
+    let mut entry_count = fields.u16()? as u64;
+    fields.u32()?;
+    let mut directory_offset = fields.u32()? as u64;
+
+    if end >= ZIP64_LOCATOR_LEN {
+        let mut locator = Fields::at(bytes, end - ZIP64_LOCATOR_LEN);
+        if locator.u32()? == ZIP64_LOCATOR_SIGNATURE {
+            locator.u32()?;
+            let mut record = Fields::at(bytes, to_usize(locator.u64()?)?);
+            if record.u32()? != ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE {
+                return Err(invalid("bad zip64 end of central directory"));
+            }
+            record.take(28)?;
+            entry_count = record.u64()?;
+            record.u64()?;
+            directory_offset = record.u64()?;
+        }
+    }
+
+    let mut fields = Fields::at(bytes, to_usize(directory_offset)?);
+    let mut entries = Vec::new();
+    for _ in 0..entry_count {
+        if fields.u32()? != CENTRAL_HEADER_SIGNATURE {
+            return Err(invalid("bad central directory header"));
+        }
+        fields.take(6)?;
+        let method = fields.u16()?;
+        fields.take(4)?;
+        let crc = fields.u32()?;
+        let mut compressed_size = fields.u32()? as u64;
+        let mut uncompressed_size = fields.u32()? as u64;
+        let name_len = fields.u16()? as usize;
+        let extra_len = fields.u16()? as usize;
+        let comment_len = fields.u16()? as usize;
+        fields.take(8)?;
+        let mut local_header_offset = fields.u32()? as u64;
+        let name = String::from_utf8_lossy(fields.take(name_len)?).into_owned();
+
+        // zip64 extra data holds, in order, the 64 bit values of whichever
+        // of these fields were saturated
+        let mut extra = Fields::at(fields.take(extra_len)?, 0);
+        while extra.position < extra.bytes.len() {
+            let id = extra.u16()?;
+            let len = extra.u16()? as usize;
+            let mut data = Fields::at(extra.take(len)?, 0);
+            if id != ZIP64_EXTRA_FIELD {
+                continue;
+            }
+            for value in [
+                &mut uncompressed_size,
+                &mut compressed_size,
+                &mut local_header_offset,
+            ] {
+                if *value == u32::MAX as u64 {
+                    *value = data.u64()?;
+                }
+            }
+        }
+        fields.take(comment_len)?;
+
+        entries.push(Entry {
+            name,
+            method,
+            crc,
+            compressed_size,
+            uncompressed_size,
+            local_header_offset,
+        });
+    }
+    Ok(entries)
+}
+
+pub struct NpzWriter<W: Write> {
+    writer: W,
+    compression: Compression,
+    entries: Vec<Entry>,
+    offset: u64,
+}
+
+impl NpzWriter<BufWriter<File>> {
+    pub fn create<P: AsRef<Path>>(path: P, compression: Compression) -> Result<Self, NpyError> {
+        Ok(NpzWriter::new(
+            BufWriter::new(File::create(path)?),
+            compression,
+        ))
+    }
+}
+
+impl<W: Write> NpzWriter<W> {
+    pub fn new(writer: W, compression: Compression) -> Self {
+        NpzWriter {
+            writer,
+            compression,
+            entries: Vec::new(),
+            offset: 0,
+        }
+    }
+
+    // stores array as `name`.npy, to be loaded in numpy as archive[name]
+    pub fn add_array<A, S, D>(
+        &mut self,
+        name: &str,
+        array: &ArrayBase<S, D>,
+    ) -> Result<(), NpyError>
+    where
+        A: NpyElement,
+        S: ndarray::Data<Elem = A>,
+        D: ndarray::Dimension,
+    {
+        let name = npy_name(name);
+        if self.entries.iter().any(|entry| entry.name == name) {
+            return Err(NpyError::InvalidArchive(format!(
+                "duplicate entry {}",
+                name
+            )));
+        }
+        let mut contents = Vec::new();
+        write_npy_to(&mut contents, array)?;
+        let data = match self.compression {
+            Compression::Stored => contents.clone(),
+            Compression::Deflated => {
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&contents)?;
+                encoder.finish()?
+            }
+        };
+        // this writer does not emit zip64 records
+        if data.len() as u64 >= u32::MAX as u64
+            || contents.len() as u64 >= u32::MAX as u64
+            || self.offset >= u32::MAX as u64
+        {
+            return Err(invalid("archive too large"));
+        }
+
+        let entry = Entry {
+            name,
+            method: self.compression.method(),
+            crc: crc32(&contents),
+            compressed_size: data.len() as u64,
+            uncompressed_size: contents.len() as u64,
+            local_header_offset: self.offset,
+        };
+        let mut header = Vec::with_capacity(LOCAL_HEADER_LEN + entry.name.len());
+        header.extend(LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        header.extend(VERSION.to_le_bytes());
+        write_common_fields(&mut header, &entry);
+        header.extend(0u16.to_le_bytes());
+        header.extend(entry.name.as_bytes());
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&data)?;
+        self.offset += (header.len() + data.len()) as u64;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    // writes the central directory and returns the underlying writer
+    pub fn finish(mut self) -> Result<W, NpyError> {
+        let mut directory = Vec::with_capacity(CENTRAL_HEADER_LEN * self.entries.len());
+        for entry in &self.entries {
+            directory.extend(CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            directory.extend(VERSION.to_le_bytes());
+            directory.extend(VERSION.to_le_bytes());
+            write_common_fields(&mut directory, entry);
+            // extra and comment lengths, disk number, internal and external attributes
+            directory.extend([0u8; 12]);
+            directory.extend((entry.local_header_offset as u32).to_le_bytes());
+            directory.extend(entry.name.as_bytes());
+        }
+        if self.entries.len() > u16::MAX as usize || self.offset >= u32::MAX as u64 {
+            return Err(invalid("archive too large"));
+        }
+
+        let mut end = Vec::with_capacity(END_OF_CENTRAL_DIRECTORY_LEN);
+        end.extend(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        end.extend([0u8; 4]);
+        end.extend((self.entries.len() as u16).to_le_bytes());
+        end.extend((self.entries.len() as u16).to_le_bytes());
+        end.extend((directory.len() as u32).to_le_bytes());
+        end.extend((self.offset as u32).to_le_bytes());
+        end.extend(0u16.to_le_bytes());
+
+        self.writer.write_all(&directory)?;
+        self.writer.write_all(&end)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// the fields from flags up to the file name length, shared by the local and central headers
+fn write_common_fields(out: &mut Vec<u8>, entry: &Entry) {
+    out.extend(FLAG_UTF8.to_le_bytes());
+    out.extend(entry.method.to_le_bytes());
+    out.extend(0u16.to_le_bytes());
+    out.extend(DOS_DATE.to_le_bytes());
+    out.extend(entry.crc.to_le_bytes());
+    out.extend((entry.compressed_size as u32).to_le_bytes());
+    out.extend((entry.uncompressed_size as u32).to_le_bytes());
+    out.extend((entry.name.len() as u16).to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::array::{Array1, Array2, Ix1};
+
+    #[test]
+    fn test_round_trip() {
+        let x = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let theta = array![0.25f32, -4.0];
+        for compression in [Compression::Stored, Compression::Deflated] {
+            let mut writer = NpzWriter::new(Vec::new(), compression);
+            writer.add_array("x", &x).unwrap();
+            writer.add_array("theta.npy", &theta).unwrap();
+            assert!(writer.add_array("x", &x).is_err());
+            let bytes = writer.finish().unwrap();
+
+            let reader = NpzReader::from_reader(bytes.as_slice()).unwrap();
+            assert_eq!(reader.names(), vec!["x", "theta"]);
+            let read_x: Array2<f64> = reader.by_name("x").unwrap();
+            let read_theta: Array1<f32> = reader.by_name("theta.npy").unwrap();
+            assert_eq!(read_x, x);
+            assert_eq!(read_theta, theta);
+            assert!(matches!(
+                reader.by_name::<f64, Ix1>("y"),
+                Err(NpyError::MissingEntry(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_corrupted_archive() {
+        let mut writer = NpzWriter::new(Vec::new(), Compression::Stored);
+        writer.add_array("x", &array![1.0, 2.0]).unwrap();
+        let mut bytes = writer.finish().unwrap();
+        // flip a bit in the last element of the data
+        bytes[LOCAL_HEADER_LEN + "x.npy".len() + 128 + 15] ^= 1;
+        let reader = NpzReader::from_reader(bytes.as_slice()).unwrap();
+        assert!(matches!(
+            reader.by_name::<f64, Ix1>("x"),
+            Err(NpyError::ChecksumMismatch(_))
+        ));
+
+        assert!(NpzReader::from_reader(&bytes[..bytes.len() - 1]).is_err());
+        assert!(NpzReader::from_reader(&b"PK"[..]).is_err());
+    }
+
+    #[test]
+    fn test_read_numpy_savez_compressed() {
+        // written by numpy.savez_compressed(path, x=x, theta=theta), which
+        // uses zip64 local headers
+        let reader = NpzReader::open("resources/savez_compressed.npz").unwrap();
+        assert_eq!(reader.names(), vec!["x", "theta"]);
+        let x: Array2<f64> = reader.by_name("x").unwrap();
+        assert_eq!(x, array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let theta: Array1<f32> = reader.by_name("theta").unwrap();
+        assert_eq!(theta, array![0.5, -1.5]);
+    }
+}
@@ -2,5 +2,24 @@ use crate::array;
 use crate::linear_regression;
 
 pub trait ParametricAlgorithm<T>: linear_regression::LinearRegressionModel<T> {
-    fn theta(&self) -> array::ArrayView1<T>;
+    fn theta(&self) -> array::ArrayView1<'_, T>;
+
+    fn named_theta(&self) -> Vec<(&str, T)>
+    where
+        Self: FeatureNames,
+        T: Copy,
+    {
+        self.feature_names()
+            .iter()
+            .map(String::as_str)
+            .zip(self.theta())
+            .map(|(name, &theta)| (name, theta))
+            .collect()
+    }
+}
+
+// implemented by models that remember the columns of x they were fitted on
+pub trait FeatureNames {
+    // one name per element of θ
+    fn feature_names(&self) -> &[String];
 }
@@ -0,0 +1,227 @@
+use crate::preprocessing::PreprocessingError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    // one indicator column per category, named feature=category
+    OneHot,
+    // a single column holding the index of the category in the vocabulary
+    Ordinal,
+}
+
+// what to do with a category that is not in the vocabulary
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnknownCategory {
+    Error,
+    // encoded as missing values, so that imputation can fill them
+    Missing,
+    // all zero for one-hot encoding, missing for ordinal encoding
+    Ignore,
+}
+
+// Maps the categories of one string column to numeric features.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryEncoder {
+    feature: String,
+    encoding: Encoding,
+    vocabulary: Vec<String>,
+    unknown: UnknownCategory,
+}
+
+impl CategoryEncoder {
+    // the vocabulary is every distinct value in sorted order
+    pub fn fit<'a, I>(
+        feature: &str,
+        values: I,
+        encoding: Encoding,
+        unknown: UnknownCategory,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut vocabulary: Vec<String> = values.into_iter().map(str::to_string).collect();
+        vocabulary.sort();
+        vocabulary.dedup();
+        Self::with_vocabulary(feature, vocabulary, encoding, unknown)
+    }
+
+    // keeps the given order, which is what ordinal encoding numbers the categories by
+    pub fn with_vocabulary(
+        feature: &str,
+        vocabulary: Vec<String>,
+        encoding: Encoding,
+        unknown: UnknownCategory,
+    ) -> Self {
+        let mut unique = Vec::with_capacity(vocabulary.len());
+        for category in vocabulary {
+            if !unique.contains(&category) {
+                unique.push(category);
+            }
+        }
+        Self {
+            feature: feature.to_string(),
+            encoding,
+            vocabulary: unique,
+            unknown,
+        }
+    }
+
+    pub fn feature(&self) -> &str {
+        &self.feature
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn vocabulary(&self) -> &[String] {
+        &self.vocabulary
+    }
+
+    // number of columns a value is encoded to
+    pub fn width(&self) -> usize {
+        match self.encoding {
+            Encoding::OneHot => self.vocabulary.len(),
+            Encoding::Ordinal => 1,
+        }
+    }
+
+    pub fn feature_names(&self) -> Vec<String> {
+        match self.encoding {
+            Encoding::OneHot => self
+                .vocabulary
+                .iter()
+                .map(|category| format!("{}={}", self.feature, category))
+                .collect(),
+            Encoding::Ordinal => vec![self.feature.clone()],
+        }
+    }
+
+    // index of the category seen most often among values, ties resolve to
+    // the earliest category of the vocabulary
+    pub fn most_frequent<'a, I>(&self, values: I) -> Option<usize>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut counts = vec![0usize; self.vocabulary.len()];
+        for value in values {
+            if let Some(index) = self
+                .vocabulary
+                .iter()
+                .position(|category| category == value)
+            {
+                counts[index] += 1;
+            }
+        }
+        let (index, &count) = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, &count)| count)?;
+        (count > 0).then_some(index)
+    }
+
+    // appends the width() columns encoding value
+    pub fn encode_into<T>(&self, value: &str, out: &mut Vec<T>) -> Result<(), PreprocessingError>
+    where
+        T: num_traits::Float,
+    {
+        let index = self
+            .vocabulary
+            .iter()
+            .position(|category| category == value);
+        match (index, self.encoding, self.unknown) {
+            (Some(index), Encoding::OneHot, _) => {
+                out.extend((0..self.width()).map(|i| if i == index { T::one() } else { T::zero() }))
+            }
+            (Some(index), Encoding::Ordinal, _) => {
+                out.push(T::from(index).ok_or(PreprocessingError::FailedCastToT)?)
+            }
+            (None, _, UnknownCategory::Error) => {
+                return Err(PreprocessingError::UnknownCategory {
+                    feature: self.feature.clone(),
+                    category: value.to_string(),
+                })
+            }
+            (None, Encoding::OneHot, UnknownCategory::Ignore) => {
+                out.extend((0..self.width()).map(|_| T::zero()))
+            }
+            (None, _, _) => self.encode_missing_into(out),
+        }
+        Ok(())
+    }
+
+    pub fn encode<T>(&self, value: &str) -> Result<Vec<T>, PreprocessingError>
+    where
+        T: num_traits::Float,
+    {
+        let mut out = Vec::with_capacity(self.width());
+        self.encode_into(value, &mut out)?;
+        Ok(out)
+    }
+
+    // missing values are NaN in every encoded column
+    pub fn encode_missing_into<T>(&self, out: &mut Vec<T>)
+    where
+        T: num_traits::Float,
+    {
+        out.extend((0..self.width()).map(|_| T::nan()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_hot() {
+        let encoder = CategoryEncoder::fit(
+            "region",
+            ["south", "north", "east", "north"],
+            Encoding::OneHot,
+            UnknownCategory::Error,
+        );
+        assert_eq!(encoder.vocabulary(), &["east", "north", "south"]);
+        assert_eq!(
+            encoder.feature_names(),
+            vec!["region=east", "region=north", "region=south"]
+        );
+        assert_eq!(encoder.encode::<f64>("north").unwrap(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(
+            encoder.encode::<f64>("west"),
+            Err(PreprocessingError::UnknownCategory {
+                feature: "region".to_string(),
+                category: "west".to_string()
+            })
+        );
+
+        let encoder = CategoryEncoder::with_vocabulary(
+            "region",
+            encoder.vocabulary().to_vec(),
+            Encoding::OneHot,
+            UnknownCategory::Ignore,
+        );
+        assert_eq!(encoder.encode::<f64>("west").unwrap(), vec![0.0; 3]);
+        assert_eq!(
+            encoder.most_frequent(["south", "west", "east", "south"]),
+            Some(2)
+        );
+        assert_eq!(encoder.most_frequent(["south", "east"]), Some(0));
+        assert_eq!(encoder.most_frequent(["west"]), None);
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let tiers = vec!["basic".to_string(), "plus".to_string(), "pro".to_string()];
+        let encoder = CategoryEncoder::with_vocabulary(
+            "tier",
+            tiers,
+            Encoding::Ordinal,
+            UnknownCategory::Missing,
+        );
+        assert_eq!(encoder.width(), 1);
+        assert_eq!(encoder.feature_names(), vec!["tier"]);
+        assert_eq!(encoder.encode::<f32>("pro").unwrap(), vec![2.0]);
+        assert_eq!(encoder.encode::<f32>("basic").unwrap(), vec![0.0]);
+        assert!(encoder.encode::<f32>("enterprise").unwrap()[0].is_nan());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::array;
+use crate::preprocessing::PreprocessingError;
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImputeStrategy {
+    // only meaningful while loading, rows with a missing value are skipped
+    DropRow,
+    Mean,
+    Median,
+    Constant(f64),
+    // ties resolve to the smallest value
+    MostFrequent,
+}
+
+// Fill values learned from training data, so that prediction-time data gets
+// the same treatment. Missing values are represented as NaN.
+#[derive(Debug, Clone)]
+pub struct Imputer<T> {
+    feature_names: Vec<String>,
+    fill_values: Vec<Option<T>>,
+}
+
+// statistic of the observed values of a column, None when it has to be left missing
+pub(crate) fn statistic<T>(mut values: Vec<T>, strategy: ImputeStrategy) -> Option<T>
+where
+    T: num_traits::Float,
+{
+    values.retain(|value| !value.is_nan());
+    let count = T::from(values.len())?;
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    match strategy {
+        ImputeStrategy::DropRow => None,
+        ImputeStrategy::Constant(value) => T::from(value),
+        _ if values.is_empty() => None,
+        ImputeStrategy::Mean => {
+            Some(values.iter().fold(T::zero(), |sum, &value| sum + value) / count)
+        }
+        ImputeStrategy::Median => {
+            let middle = values.len() / 2;
+            if values.len().is_multiple_of(2) {
+                Some((values[middle - 1] + values[middle]) / (T::one() + T::one()))
+            } else {
+                Some(values[middle])
+            }
+        }
+        ImputeStrategy::MostFrequent => {
+            let mut best = (values[0], 0);
+            let mut run = (values[0], 0);
+            for &value in &values {
+                if value == run.0 {
+                    run.1 += 1;
+                } else {
+                    run = (value, 1);
+                }
+                if run.1 > best.1 {
+                    best = run;
+                }
+            }
+            Some(best.0)
+        }
+    }
+}
+
+impl<T> Imputer<T>
+where
+    T: num_traits::Float,
+{
+    // one strategy per column of x, None for columns that must not have missing values
+    pub fn fit<S>(
+        x: &array::ArrayBase<S, array::Ix2>,
+        feature_names: &[String],
+        strategies: &[Option<ImputeStrategy>],
+    ) -> Result<Self, PreprocessingError>
+    where
+        S: ndarray::Data<Elem = T>,
+    {
+        for len in [feature_names.len(), strategies.len()] {
+            if len != x.ncols() {
+                return Err(PreprocessingError::ColumnCountMismatch {
+                    expected: x.ncols(),
+                    found: len,
+                });
+            }
+        }
+        let fill_values = strategies
+            .iter()
+            .enumerate()
+            .map(|(j, strategy)| match strategy {
+                None | Some(ImputeStrategy::DropRow) => Ok(None),
+                Some(strategy) => statistic(x.column(j).into_iter().copied().collect(), *strategy)
+                    .map(Some)
+                    .ok_or_else(|| PreprocessingError::NoObservedValues {
+                        feature: feature_names[j].clone(),
+                    }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            feature_names: feature_names.to_vec(),
+            fill_values,
+        })
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    // None for columns that are not imputed
+    pub fn fill_values(&self) -> &[Option<T>] {
+        &self.fill_values
+    }
+
+    // replaces every NaN of x by the fill value of its column, x is left
+    // untouched if a column without fill value has a missing value
+    pub fn transform<S>(
+        &self,
+        x: &mut array::ArrayBase<S, array::Ix2>,
+    ) -> Result<(), PreprocessingError>
+    where
+        S: ndarray::DataMut<Elem = T>,
+    {
+        if x.ncols() != self.fill_values.len() {
+            return Err(PreprocessingError::ColumnCountMismatch {
+                expected: self.fill_values.len(),
+                found: x.ncols(),
+            });
+        }
+        for i in 0..x.nrows() {
+            for (j, fill_value) in self.fill_values.iter().enumerate() {
+                if fill_value.is_none() && x[(i, j)].is_nan() {
+                    return Err(PreprocessingError::MissingValue {
+                        feature: self.feature_names[j].clone(),
+                        row: i,
+                    });
+                }
+            }
+        }
+        for (j, fill_value) in self.fill_values.iter().enumerate() {
+            if let Some(fill_value) = *fill_value {
+                for value in x.column_mut(j) {
+                    if value.is_nan() {
+                        *value = fill_value;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics() {
+        let values = vec![3.0, f64::NAN, 1.0, 2.0, 2.0, 7.0];
+        assert_eq!(statistic(values.clone(), ImputeStrategy::Mean), Some(3.0));
+        assert_eq!(statistic(values.clone(), ImputeStrategy::Median), Some(2.0));
+        assert_eq!(
+            statistic(values.clone(), ImputeStrategy::MostFrequent),
+            Some(2.0)
+        );
+        assert_eq!(
+            statistic(values.clone(), ImputeStrategy::Constant(-1.0)),
+            Some(-1.0)
+        );
+        assert_eq!(statistic(values, ImputeStrategy::DropRow), None);
+        assert_eq!(
+            statistic(vec![4.0, 1.0, 2.0, 8.0], ImputeStrategy::Median),
+            Some(3.0)
+        );
+        assert_eq!(
+            statistic(vec![5.0, 4.0], ImputeStrategy::MostFrequent),
+            Some(4.0)
+        );
+        assert_eq!(statistic(vec![f64::NAN], ImputeStrategy::Mean), None);
+    }
+
+    #[test]
+    fn test_fit_transform() {
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let x = array![[1.0, f64::NAN, 5.0], [f64::NAN, 4.0, 6.0], [3.0, 8.0, 7.0]];
+        let imputer = Imputer::fit(
+            &x,
+            &names,
+            &[
+                Some(ImputeStrategy::Mean),
+                Some(ImputeStrategy::Median),
+                None,
+            ],
+        )
+        .unwrap();
+        assert_eq!(imputer.fill_values(), &[Some(2.0), Some(6.0), None]);
+
+        let mut filled = x.clone();
+        imputer.transform(&mut filled).unwrap();
+        assert_eq!(
+            filled,
+            array![[1.0, 6.0, 5.0], [2.0, 4.0, 6.0], [3.0, 8.0, 7.0]]
+        );
+
+        // prediction-time data reuses the training statistics
+        let mut new = array![[f64::NAN, f64::NAN, 0.0]];
+        imputer.transform(&mut new).unwrap();
+        assert_eq!(new, array![[2.0, 6.0, 0.0]]);
+
+        let mut unfillable = array![[0.0, 0.0, f64::NAN]];
+        assert_eq!(
+            imputer.transform(&mut unfillable),
+            Err(PreprocessingError::MissingValue {
+                feature: "c".to_string(),
+                row: 0
+            })
+        );
+        assert!(unfillable[(0, 2)].is_nan());
+        assert!(imputer.transform(&mut array![[0.0, 0.0]]).is_err());
+
+        let all_missing = array![[f64::NAN], [f64::NAN]];
+        assert_eq!(
+            Imputer::fit(&all_missing, &names[..1], &[Some(ImputeStrategy::Median)]).unwrap_err(),
+            PreprocessingError::NoObservedValues {
+                feature: "a".to_string()
+            }
+        );
+    }
+}
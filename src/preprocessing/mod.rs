@@ -0,0 +1,84 @@
+pub mod encoder;
+pub mod imputer;
+pub mod scaler;
+
+pub use crate::preprocessing::encoder::{CategoryEncoder, Encoding, UnknownCategory};
+pub use crate::preprocessing::imputer::{ImputeStrategy, Imputer};
+pub use crate::preprocessing::scaler::{Scaler, Scaling, ScalingMethod};
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreprocessingError {
+    // a column whose statistic cannot be computed because every value is missing
+    NoObservedValues {
+        feature: String,
+    },
+    // a missing value in a column that has no fill value
+    MissingValue {
+        feature: String,
+        row: usize,
+    },
+    // number of columns the transformer was fitted on and the number it was given
+    ColumnCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    UnknownCategory {
+        feature: String,
+        category: String,
+    },
+    // a value of a numeric feature that does not parse as a number
+    InvalidNumber {
+        feature: String,
+        text: String,
+    },
+    // a strategy that would break the encoding of a one-hot encoded feature
+    UnsupportedStrategy {
+        feature: String,
+        strategy: ImputeStrategy,
+    },
+    FailedCastToT,
+    // raw θ needs a constant column to hold the offsets removed by scaling
+    NoIntercept,
+}
+
+impl fmt::Display for PreprocessingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreprocessingError::NoObservedValues { feature } => {
+                write!(f, "feature '{}' has no observed values", feature)
+            }
+            PreprocessingError::MissingValue { feature, row } => {
+                write!(f, "missing value for feature '{}' in row {}", feature, row)
+            }
+            PreprocessingError::ColumnCountMismatch { expected, found } => {
+                write!(f, "expected {} columns, found {}", expected, found)
+            }
+            PreprocessingError::UnknownCategory { feature, category } => {
+                write!(
+                    f,
+                    "unknown category '{}' for feature '{}'",
+                    category, feature
+                )
+            }
+            PreprocessingError::InvalidNumber { feature, text } => {
+                write!(f, "'{}' is not a number for feature '{}'", text, feature)
+            }
+            PreprocessingError::UnsupportedStrategy { feature, strategy } => {
+                write!(
+                    f,
+                    "{:?} imputation is not supported for one-hot encoded feature '{}'",
+                    strategy, feature
+                )
+            }
+            PreprocessingError::FailedCastToT => write!(f, "value does not fit the element type"),
+            PreprocessingError::NoIntercept => {
+                write!(f, "no intercept column to map scaled θ back to raw units")
+            }
+        }
+    }
+}
+
+impl Error for PreprocessingError {}
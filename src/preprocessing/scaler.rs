@@ -0,0 +1,375 @@
+use crate::array;
+use crate::preprocessing::PreprocessingError;
+use crate::training_data::TrainingData;
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalingMethod {
+    // zero mean and unit standard deviation
+    Standard,
+    // values of the training data map to [0, 1]
+    MinMax,
+    // zero median and unit interquartile range, not thrown off by outliers
+    Robust,
+}
+
+// x' = (x - offset) / scale for one column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaling<T> {
+    pub offset: T,
+    pub scale: T,
+}
+
+// quantile of sorted values, interpolating linearly between neighbours
+fn quantile<T: num_traits::Float>(sorted: &[T], q: T) -> Option<T> {
+    let position = q * T::from(sorted.len() - 1)?;
+    let below = position.floor();
+    let i = below.to_usize()?;
+    let fraction = position - below;
+    match sorted.get(i + 1) {
+        Some(&above) => Some(sorted[i] + (above - sorted[i]) * fraction),
+        None => Some(sorted[i]),
+    }
+}
+
+impl<T> Scaling<T>
+where
+    T: num_traits::Float,
+{
+    // missing values are ignored, None when there is no observed value. Columns
+    // without spread, such as the intercept, are left unchanged
+    pub fn fit(values: impl IntoIterator<Item = T>, method: ScalingMethod) -> Option<Self> {
+        let mut values: Vec<T> = values.into_iter().filter(|value| !value.is_nan()).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let count = T::from(values.len())?;
+        let (offset, scale) = match method {
+            _ if values.is_empty() => return None,
+            ScalingMethod::Standard => {
+                let mean = values.iter().fold(T::zero(), |sum, &value| sum + value) / count;
+                let variance = values.iter().fold(T::zero(), |sum, &value| {
+                    sum + (value - mean) * (value - mean)
+                }) / count;
+                (mean, variance.sqrt())
+            }
+            ScalingMethod::MinMax => (values[0], values[values.len() - 1] - values[0]),
+            ScalingMethod::Robust => {
+                let quarter = T::from(0.25)?;
+                let median = quantile(&values, quarter + quarter)?;
+                let lower = quantile(&values, quarter)?;
+                let upper = quantile(&values, T::one() - quarter)?;
+                (median, upper - lower)
+            }
+        };
+        if scale > T::zero() {
+            Some(Self { offset, scale })
+        } else {
+            Some(Self::identity())
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self {
+            offset: T::zero(),
+            scale: T::one(),
+        }
+    }
+
+    pub fn apply(&self, value: T) -> T {
+        (value - self.offset) / self.scale
+    }
+
+    pub fn invert(&self, value: T) -> T {
+        value * self.scale + self.offset
+    }
+}
+
+// Scales every column of x, and optionally y, with statistics learned from
+// training data. θ fitted on scaled data can be mapped back to raw units.
+#[derive(Debug, Clone)]
+pub struct Scaler<T> {
+    method: ScalingMethod,
+    feature_names: Vec<String>,
+    features: Vec<Scaling<T>>,
+    target: Option<Scaling<T>>,
+    // first column that is constant and non zero, which absorbs the offsets in raw θ
+    intercept: Option<(usize, T)>,
+}
+
+impl<T> Scaler<T>
+where
+    T: num_traits::Float,
+{
+    pub fn fit<S>(
+        x: &array::ArrayBase<S, array::Ix2>,
+        feature_names: &[String],
+        method: ScalingMethod,
+    ) -> Result<Self, PreprocessingError>
+    where
+        S: ndarray::Data<Elem = T>,
+    {
+        if feature_names.len() != x.ncols() {
+            return Err(PreprocessingError::ColumnCountMismatch {
+                expected: x.ncols(),
+                found: feature_names.len(),
+            });
+        }
+        let features = (0..x.ncols())
+            .map(|j| {
+                Scaling::fit(x.column(j).into_iter().copied(), method).ok_or_else(|| {
+                    PreprocessingError::NoObservedValues {
+                        feature: feature_names[j].clone(),
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let intercept = (0..x.ncols()).find_map(|j| {
+            let first = *x.column(j).into_iter().next()?;
+            let constant = x.column(j).into_iter().all(|&value| value == first);
+            (constant && first != T::zero()).then_some((j, first))
+        });
+        Ok(Self {
+            method,
+            feature_names: feature_names.to_vec(),
+            features,
+            target: None,
+            intercept,
+        })
+    }
+
+    // scales the features, and the target too when scale_target is set
+    pub fn fit_training_data(
+        data: &TrainingData<T>,
+        method: ScalingMethod,
+        scale_target: bool,
+    ) -> Result<Self, PreprocessingError> {
+        let mut scaler = Self::fit(&data.x, &data.feature_names, method)?;
+        if scale_target {
+            scaler.target = Some(
+                Scaling::fit(data.y.into_iter().copied(), method).ok_or_else(|| {
+                    PreprocessingError::NoObservedValues {
+                        feature: data.target_name.clone(),
+                    }
+                })?,
+            );
+        }
+        Ok(scaler)
+    }
+
+    pub fn method(&self) -> ScalingMethod {
+        self.method
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    pub fn feature_scalings(&self) -> &[Scaling<T>] {
+        &self.features
+    }
+
+    pub fn target_scaling(&self) -> Option<&Scaling<T>> {
+        self.target.as_ref()
+    }
+
+    pub fn transform<S>(
+        &self,
+        x: &mut array::ArrayBase<S, array::Ix2>,
+    ) -> Result<(), PreprocessingError>
+    where
+        S: ndarray::DataMut<Elem = T>,
+    {
+        self.map_columns(x, Scaling::apply)
+    }
+
+    pub fn inverse_transform<S>(
+        &self,
+        x: &mut array::ArrayBase<S, array::Ix2>,
+    ) -> Result<(), PreprocessingError>
+    where
+        S: ndarray::DataMut<Elem = T>,
+    {
+        self.map_columns(x, Scaling::invert)
+    }
+
+    // no-op when the target is not scaled
+    pub fn transform_target<S>(&self, y: &mut array::ArrayBase<S, array::Ix1>)
+    where
+        S: ndarray::DataMut<Elem = T>,
+    {
+        if let Some(target) = &self.target {
+            y.inner_impl_mut().mapv_inplace(|value| target.apply(value));
+        }
+    }
+
+    // maps predictions made on scaled data back to raw units
+    pub fn inverse_transform_target<S>(&self, y: &mut array::ArrayBase<S, array::Ix1>)
+    where
+        S: ndarray::DataMut<Elem = T>,
+    {
+        if let Some(target) = &self.target {
+            y.inner_impl_mut()
+                .mapv_inplace(|value| target.invert(value));
+        }
+    }
+
+    pub fn transform_training_data(
+        &self,
+        data: &mut TrainingData<T>,
+    ) -> Result<(), PreprocessingError> {
+        self.transform(&mut data.x)?;
+        self.transform_target(&mut data.y);
+        Ok(())
+    }
+
+    // θ of a model fitted on scaled data, expressed for raw x and y:
+    // y = o_y + s_y Σ θ'ⱼ (xⱼ - oⱼ) / sⱼ, so θⱼ = s_y θ'ⱼ / sⱼ and the
+    // remaining constant goes to the intercept column
+    pub fn unscale_theta<S>(
+        &self,
+        theta: &array::ArrayBase<S, array::Ix1>,
+    ) -> Result<array::Array1<T>, PreprocessingError>
+    where
+        S: ndarray::Data<Elem = T>,
+    {
+        if theta.len() != self.features.len() {
+            return Err(PreprocessingError::ColumnCountMismatch {
+                expected: self.features.len(),
+                found: theta.len(),
+            });
+        }
+        let target = self.target.unwrap_or_else(Scaling::identity);
+        let mut constant = target.offset;
+        let mut raw = Vec::with_capacity(theta.len());
+        for (&theta, scaling) in theta.into_iter().zip(&self.features) {
+            let value = target.scale * theta / scaling.scale;
+            constant = constant - value * scaling.offset;
+            raw.push(value);
+        }
+        if constant != T::zero() {
+            let (j, intercept) = self.intercept.ok_or(PreprocessingError::NoIntercept)?;
+            raw[j] = raw[j] + constant / intercept;
+        }
+        Ok(array::Array1::from_vec(raw))
+    }
+
+    fn map_columns<S, F>(
+        &self,
+        x: &mut array::ArrayBase<S, array::Ix2>,
+        f: F,
+    ) -> Result<(), PreprocessingError>
+    where
+        S: ndarray::DataMut<Elem = T>,
+        F: Fn(&Scaling<T>, T) -> T,
+    {
+        if x.ncols() != self.features.len() {
+            return Err(PreprocessingError::ColumnCountMismatch {
+                expected: self.features.len(),
+                found: x.ncols(),
+            });
+        }
+        for (j, scaling) in self.features.iter().enumerate() {
+            for value in x.column_mut(j) {
+                *value = f(scaling, *value);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fittable_model::{FittableModel, FittingSettings};
+    use crate::gradient_descent::GradientDescent;
+    use crate::lms::BatchKernel;
+    use crate::normal_equation::NormalEquation;
+    use crate::parametric_algorithm::ParametricAlgorithm;
+    use crate::training_data::read_data;
+
+    #[test]
+    fn test_scalings() {
+        let values = [4.0, 1.0, f64::NAN, 3.0, 2.0, 100.0];
+        let standard = Scaling::fit(values, ScalingMethod::Standard).unwrap();
+        assert_eq!(standard.offset, 22.0);
+        let min_max = Scaling::fit(values, ScalingMethod::MinMax).unwrap();
+        assert_eq!(
+            min_max,
+            Scaling {
+                offset: 1.0,
+                scale: 99.0
+            }
+        );
+        assert_eq!(min_max.apply(100.0), 1.0);
+        let robust = Scaling::fit(values, ScalingMethod::Robust).unwrap();
+        assert_eq!(
+            robust,
+            Scaling {
+                offset: 3.0,
+                scale: 2.0
+            }
+        );
+        assert_eq!(robust.invert(robust.apply(7.5)), 7.5);
+
+        assert_eq!(
+            Scaling::fit([1.0, 1.0], ScalingMethod::Standard),
+            Some(Scaling::identity())
+        );
+        assert_eq!(Scaling::<f64>::fit([f64::NAN], ScalingMethod::MinMax), None);
+    }
+
+    #[test]
+    fn test_transform() {
+        let names = vec!["intercept".to_string(), "a".to_string()];
+        let x = array![[1.0, 2.0], [1.0, 4.0], [1.0, 6.0]];
+        let scaler = Scaler::fit(&x, &names, ScalingMethod::MinMax).unwrap();
+        let mut scaled = x.clone();
+        scaler.transform(&mut scaled).unwrap();
+        assert_eq!(scaled, array![[1.0, 0.0], [1.0, 0.5], [1.0, 1.0]]);
+        scaler.inverse_transform(&mut scaled).unwrap();
+        assert_eq!(scaled, x);
+        assert!(scaler.transform(&mut array![[1.0]]).is_err());
+
+        // y = 1 + 2a on raw data is y' = 0 + 1a' on standardized data
+        let mut data = TrainingData::new(x.clone(), array![5.0, 9.0, 13.0]);
+        let scaler = Scaler::fit_training_data(&data, ScalingMethod::Standard, true).unwrap();
+        scaler.transform_training_data(&mut data).unwrap();
+        assert_eq!(data.y, data.x.column(1));
+        let theta: array::Array1<f64> = scaler.unscale_theta(&array![0.0, 1.0]).unwrap();
+        assert!((theta[0] - 1.0).abs() < 1e-12 && (theta[1] - 2.0).abs() < 1e-12);
+
+        let mut predictions = array![0.0];
+        scaler.inverse_transform_target(&mut predictions);
+        assert_eq!(predictions, array![9.0]);
+
+        let without_intercept =
+            Scaler::fit(&array![[2.0], [4.0]], &names[1..], ScalingMethod::Standard).unwrap();
+        assert_eq!(
+            without_intercept.unscale_theta(&array![1.0]),
+            Err(PreprocessingError::NoIntercept)
+        );
+    }
+
+    #[test]
+    fn test_gradient_descent_on_scaled_data() {
+        let raw = read_data::<f64>("resources/3.csv").unwrap();
+        let exact = NormalEquation::new(&raw).unwrap();
+
+        let scaler = Scaler::fit_training_data(&raw, ScalingMethod::Standard, true).unwrap();
+        let mut scaled = raw.clone();
+        scaler.transform_training_data(&mut scaled).unwrap();
+        let settings = FittingSettings {
+            max_iteration_count: 1000,
+            learning_rate: 0.01,
+            eps: 1e-14,
+            starting_theta: array::Array1::zeros(scaled.x.ncols()),
+            batch_size: 1,
+        };
+        let model = GradientDescent::fit::<BatchKernel>(&scaled, &settings).unwrap();
+        assert!(model.fitting_info().iteration_count < 1000);
+        let theta = scaler.unscale_theta(&model.theta()).unwrap();
+        for (raw, exact) in theta.into_iter().zip(exact.theta()) {
+            assert!((raw - exact).abs() < 1e-6 * exact.abs());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// SplitMix64 (Steele, Lea and Flood), small and fast with a fully specified
+// output, so a seed gives the same sequence on every platform including wasm.
+// Not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in [0, bound), computed in 64 bits so that the result does not
+    // depend on the width of usize
+    pub fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u64;
+        assert!(bound > 0, "bound must be positive");
+        // rejects the top values that would make the low ones more likely
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    // Fisher-Yates
+    pub fn shuffle<X>(&mut self, items: &mut [X]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// the indices 0..len in the order given by seed
+pub fn permutation(len: usize, seed: u64) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    SplitMix64::new(seed).shuffle(&mut indices);
+    indices
+}
+
+// a seed from the operating system, or the browser on wasm, for when runs
+// need not be reproducible
+pub fn random_seed() -> Result<u64, getrandom::Error> {
+    let mut bytes = [0u8; 8];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_sequence() {
+        // first outputs of the reference implementation for seed 1234567
+        let mut random = SplitMix64::new(1234567);
+        let expected = [
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+        ];
+        for value in expected {
+            assert_eq!(random.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_permutation() {
+        // pinned so that a change of the sequence on some platform shows up
+        let permutation = permutation(10, 42);
+        assert_eq!(permutation, vec![0, 9, 5, 8, 6, 4, 7, 2, 1, 3]);
+        assert_ne!(permutation, super::permutation(10, 43));
+        let mut sorted = permutation.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+        assert!(super::permutation(0, 42).is_empty());
+    }
+}
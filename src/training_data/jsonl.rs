@@ -0,0 +1,233 @@
+use crate::training_data::{
+    build, RawRow, RawTable, ReadOptions, TrainingData, TrainingDataError, INTERCEPT,
+};
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+pub fn read_jsonl<T>(path: impl AsRef<Path>) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    read_jsonl_with(path, &ReadOptions::default())
+}
+
+// One sample per line, either an object keyed by column name or an array of
+// cells that is treated like a csv row, header detection included. Keys that
+// a line lacks and null values are missing.
+pub fn read_jsonl_with<T>(
+    path: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|source| TrainingDataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // column of every key when the lines are objects, in the order keys are first seen
+    let mut keys: Option<HashMap<String, usize>> = None;
+    let mut header = Vec::new();
+    let mut rows = Vec::new();
+    for (line, text) in (1..).zip(text.lines()) {
+        let trimmed = text.trim();
+        let is_comment = options
+            .comment
+            .is_some_and(|comment| trimmed.as_bytes().first() == Some(&comment));
+        if trimmed.is_empty() || is_comment {
+            continue;
+        }
+        let syntax_error = |message: String| TrainingDataError::Syntax {
+            path: path.to_path_buf(),
+            line,
+            message,
+        };
+        let value: Value =
+            serde_json::from_str(trimmed).map_err(|error| syntax_error(error.to_string()))?;
+        let cells = match value {
+            Value::Object(object) if rows.is_empty() || keys.is_some() => {
+                let keys = keys.get_or_insert_with(HashMap::new);
+                let mut cells = vec![None; header.len()];
+                for (key, value) in object {
+                    let index = *keys.entry(key.clone()).or_insert_with(|| {
+                        header.push(key);
+                        header.len() - 1
+                    });
+                    cells.resize(header.len(), None);
+                    cells[index] = cell(value);
+                }
+                cells
+            }
+            Value::Array(values) if keys.is_none() => values.into_iter().map(cell).collect(),
+            _ => {
+                return Err(syntax_error(
+                    "expected every line to be an object or every line to be an array".to_string(),
+                ))
+            }
+        };
+        rows.push((line, cells));
+    }
+
+    if keys.is_some() {
+        // columns first seen further down the file are missing in earlier rows
+        let rows = rows
+            .into_iter()
+            .map(|(line, mut cells)| {
+                cells.resize(header.len(), None);
+                RawRow::dense(line, cells)
+            })
+            .collect();
+        return build(path, RawTable { header, rows }, options);
+    }
+    if let Some(expected) = rows.first().map(|(_, cells)| cells.len()) {
+        if let Some((line, cells)) = rows.iter().find(|(_, cells)| cells.len() != expected) {
+            return Err(TrainingDataError::RaggedRow {
+                path: path.to_path_buf(),
+                line: *line,
+                expected,
+                found: cells.len(),
+            });
+        }
+    }
+    let rows = rows
+        .into_iter()
+        .map(|(line, cells)| RawRow::dense(line, cells))
+        .collect();
+    build(path, RawTable::split_header::<T>(rows, options), options)
+}
+
+fn cell(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text),
+        // numbers keep their text, so that they are parsed once, straight to T
+        value => Some(value.to_string()),
+    }
+}
+
+// writes one object per sample, keyed by the feature names and the target
+// name. The intercept column is left out and values that JSON cannot hold,
+// such as NaN, are written as null
+pub fn write_jsonl<T>(
+    path: impl AsRef<Path>,
+    data: &TrainingData<T>,
+) -> Result<(), TrainingDataError>
+where
+    T: num_traits::Float + fmt::Display,
+{
+    let path = path.as_ref();
+    let io_error = |source| TrainingDataError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let key = |name: &str| Value::String(name.to_string()).to_string();
+    let columns: Vec<(usize, String)> = data
+        .feature_names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| name.as_str() != INTERCEPT)
+        .map(|(j, name)| (j, key(name)))
+        .collect();
+    let target = key(&data.target_name);
+    let value = |value: T| {
+        if value.is_finite() {
+            value.to_string()
+        } else {
+            "null".to_string()
+        }
+    };
+
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    for (i, &y) in data.y.into_iter().enumerate() {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|(j, key)| format!("{}:{}", key, value(data.x[(i, *j)])))
+            .chain(std::iter::once(format!("{}:{}", target, value(y))))
+            .collect();
+        writeln!(writer, "{{{}}}", fields.join(",")).map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::training_data::tests::write_temp;
+
+    #[test]
+    fn test_read_jsonl() {
+        let path = write_temp(
+            "objects.jsonl",
+            "{\"size\": 1.5, \"region\": \"north\", \"price\": 3}\n\n{\"price\": 5, \"size\": 2.5, \"region\": null, \"rooms\": 4}\n",
+        );
+        let options = ReadOptions {
+            target: Some("price".into()),
+            features: Some(vec!["size".into(), "rooms".into()]),
+            default_imputation: Some(crate::preprocessing::ImputeStrategy::Constant(0.0)),
+            ..ReadOptions::default()
+        };
+        let data = read_jsonl_with::<f64>(&path, &options).unwrap();
+        assert_eq!(data.feature_names, vec![INTERCEPT, "size", "rooms"]);
+        assert_eq!(data.x, array![[1.0, 1.5, 0.0], [1.0, 2.5, 4.0]]);
+        assert_eq!(data.y, array![3.0, 5.0]);
+        assert!(matches!(
+            read_jsonl::<f64>(&path),
+            Err(TrainingDataError::MissingValue { line: 1, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        let path = write_temp("arrays.jsonl", "[1.2, 3]\n[2.0, 4]\n{\"x\": 1}\n");
+        assert!(matches!(
+            read_jsonl::<f64>(&path),
+            Err(TrainingDataError::Syntax { line: 3, .. })
+        ));
+        std::fs::write(&path, "[1.2, 3]\n[2.0, 4]\n").unwrap();
+        let data = read_jsonl::<f32>(&path).unwrap();
+        assert_eq!(data.feature_names, vec![INTERCEPT, "x_1"]);
+        assert_eq!(data.y, array![3.0, 4.0]);
+        std::fs::write(&path, "[1.2, 3]\n[2.0, 4\n").unwrap();
+        let error = read_jsonl::<f64>(&path).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_jsonl() {
+        let mut data = TrainingData::new(
+            array![[1.0, 0.1, f64::NAN], [1.0, 1.2000000000000002, -3.0]],
+            array![2.0, 0.5],
+        );
+        data.feature_names = vec![
+            INTERCEPT.to_string(),
+            "a \"b\"".to_string(),
+            "c".to_string(),
+        ];
+        let path = write_temp("written.jsonl", "");
+        write_jsonl(&path, &data).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"a \\\"b\\\"\":0.1,\"c\":null,\"y\":2}\n{\"a \\\"b\\\"\":1.2000000000000002,\"c\":-3,\"y\":0.5}\n"
+        );
+        let options = ReadOptions {
+            default_imputation: Some(crate::preprocessing::ImputeStrategy::Constant(0.0)),
+            ..ReadOptions::default()
+        };
+        let read = read_jsonl_with::<f64>(&path, &options).unwrap();
+        assert_eq!(read.feature_names, data.feature_names);
+        assert_eq!(read.y, data.y);
+        assert_eq!(read.x.row(1), data.x.row(1));
+        assert_eq!(read.x[(0, 2)], 0.0);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
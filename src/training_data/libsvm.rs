@@ -0,0 +1,266 @@
+use crate::training_data::{
+    build, Cells, RawRow, RawTable, ReadOptions, TrainingData, TrainingDataError, INTERCEPT,
+};
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+// largest feature index read_libsvm accepts
+const MAX_FEATURES: usize = 1 << 24;
+// largest number of values of the dense x read_libsvm builds, 2 GiB of f64
+const MAX_VALUES: usize = 1 << 28;
+
+pub fn read_libsvm<T>(path: impl AsRef<Path>) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    read_libsvm_with(path, &ReadOptions::default())
+}
+
+// Sparse "label index:value ..." lines with 1-based feature indices, as used by
+// LIBSVM and SVMlight. Feature i is named x_i and the label y, absent features
+// are 0. SVMlight qid tokens and comments after # are skipped.
+pub fn read_libsvm_with<T>(
+    path: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|source| TrainingDataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let max_index = options.libsvm_features.unwrap_or(MAX_FEATURES);
+    let mut samples = Vec::new();
+    let mut columns = options.libsvm_features.unwrap_or(0);
+    for (line, text) in (1..).zip(text.lines()) {
+        let text = text.split('#').next().unwrap_or_default();
+        let mut tokens = text.split_whitespace();
+        let Some(label) = tokens.next() else {
+            continue;
+        };
+        let syntax_error = |message: String| TrainingDataError::Syntax {
+            path: path.to_path_buf(),
+            line,
+            message,
+        };
+        let mut features = Vec::new();
+        for token in tokens.filter(|token| !token.starts_with("qid:")) {
+            let (index, value) = token
+                .split_once(':')
+                .ok_or_else(|| syntax_error(format!("expected index:value, found '{}'", token)))?;
+            // checked before x_1 up to x_index are named and allocated
+            let index = index
+                .parse::<usize>()
+                .ok()
+                .filter(|&index| index > 0 && index <= max_index)
+                .ok_or_else(|| syntax_error(format!("invalid feature index '{}'", index)))?;
+            features.push((index - 1, value.to_string()));
+        }
+        features.sort_by_key(|&(column, _)| column);
+        if let Some(pair) = features.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(syntax_error(format!(
+                "feature {} is given twice",
+                pair[0].0 + 1
+            )));
+        }
+        columns = columns.max(features.last().map_or(0, |&(column, _)| column + 1));
+        samples.push((line, label.to_string(), features));
+    }
+
+    // x is dense, so a few lines with a large index could otherwise need far
+    // more memory than the file
+    let x_columns =
+        options.features.as_ref().map_or(columns, Vec::len) + options.intercept as usize;
+    if samples
+        .len()
+        .checked_mul(x_columns)
+        .is_none_or(|values| values > MAX_VALUES)
+    {
+        return Err(TrainingDataError::TooLarge {
+            path: path.to_path_buf(),
+            rows: samples.len(),
+            columns: x_columns,
+        });
+    }
+
+    let header = (1..=columns)
+        .map(|i| format!("x_{}", i))
+        .chain(std::iter::once("y".to_string()))
+        .collect();
+    // only the listed features are kept, the label is the last column
+    let rows = samples
+        .into_iter()
+        .map(|(line, label, mut cells)| {
+            cells.push((columns, label));
+            RawRow {
+                line,
+                cells: Cells::Sparse {
+                    len: columns + 1,
+                    cells,
+                },
+            }
+        })
+        .collect();
+    build(path, RawTable { header, rows }, options)
+}
+
+// writes the target as label followed by the non zero features, numbered from
+// 1 in the order of the columns of x. The intercept column is left out. Columns
+// that are 0 in every row at the end of x leave no trace in the file, so reading
+// it back needs ReadOptions::libsvm_features to restore them
+pub fn write_libsvm<T>(
+    path: impl AsRef<Path>,
+    data: &TrainingData<T>,
+) -> Result<(), TrainingDataError>
+where
+    T: num_traits::Float + fmt::Display,
+{
+    let path = path.as_ref();
+    let io_error = |source| TrainingDataError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let columns: Vec<usize> = (0..data.x.ncols())
+        .filter(|&j| data.feature_names.get(j).map(String::as_str) != Some(INTERCEPT))
+        .collect();
+
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    for (i, y) in data.y.into_iter().enumerate() {
+        write!(writer, "{}", y).map_err(io_error)?;
+        for (index, &j) in (1..).zip(&columns) {
+            let value = data.x[(i, j)];
+            if value != T::zero() {
+                write!(writer, " {}:{}", index, value).map_err(io_error)?;
+            }
+        }
+        writeln!(writer).map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+    use crate::training_data::tests::write_temp;
+
+    #[test]
+    fn test_read_libsvm() {
+        let path = write_temp(
+            "sparse.svm",
+            "# benchmark\n1 qid:3 1:0.5 3:-2\n\n-1 2:1e-3 # negative\n+1\n",
+        );
+        let data = read_libsvm::<f64>(&path).unwrap();
+        assert_eq!(data.feature_names, vec![INTERCEPT, "x_1", "x_2", "x_3"]);
+        assert_eq!(
+            data.x,
+            array![
+                [1.0, 0.5, 0.0, -2.0],
+                [1.0, 0.0, 0.001, 0.0],
+                [1.0, 0.0, 0.0, 0.0]
+            ]
+        );
+        assert_eq!(data.y, array![1.0, -1.0, 1.0]);
+
+        let options = ReadOptions {
+            features: Some(vec!["x_3".into()]),
+            intercept: false,
+            ..ReadOptions::default()
+        };
+        let data = read_libsvm_with::<f32>(&path, &options).unwrap();
+        assert_eq!(data.x, array![[-2.0], [0.0], [0.0]]);
+
+        for (contents, line) in [
+            ("1 1:2\n1 0:2\n", 2),
+            ("1 1:2 1:3\n", 1),
+            ("1 2\n", 1),
+            ("1 1:2\n1 99999999999:1\n", 2),
+        ] {
+            std::fs::write(&path, contents).unwrap();
+            let error = read_libsvm::<f64>(&path).unwrap_err();
+            assert!(matches!(error, TrainingDataError::Syntax { .. }));
+            assert_eq!(error.line(), Some(line));
+        }
+        std::fs::write(&path, "1 1:2\nyes 1:3\n").unwrap();
+        assert!(matches!(
+            read_libsvm::<f64>(&path),
+            Err(TrainingDataError::Parse { line: 2, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_too_large_libsvm() {
+        let path = write_temp("too_large.svm", &"1 16777216:1\n".repeat(100));
+        assert!(matches!(
+            read_libsvm::<f64>(&path),
+            Err(TrainingDataError::TooLarge {
+                rows: 100,
+                columns: 16777217,
+                ..
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_wide_libsvm() {
+        let path = write_temp("wide.svm", "1 3:1 100000:2\n0 7:3\n");
+        let data = read_libsvm::<f64>(&path).unwrap();
+        assert_eq!(data.x.dim(), (2, 100001));
+        assert_eq!(data.feature_names[100000], "x_100000");
+        assert_eq!(data.x.row(0).sum(), 4.0);
+        assert_eq!((data.x[(0, 100000)], data.x[(1, 7)]), (2.0, 3.0));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_libsvm() {
+        let data = sample_data();
+        let path = write_temp("written.svm", "");
+        write_libsvm(&path, &data).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "3 1:1.5 2:-1\n0.25 2:2\n"
+        );
+        let read = read_libsvm::<f64>(&path).unwrap();
+        assert_eq!(read.x, data.x);
+        assert_eq!(read.y, data.y);
+
+        // a last column of zeros is only restored with the feature count
+        let data = TrainingData::new(array![[1.0, 1.5, 0.0], [1.0, -2.0, 0.0]], array![1.0, 0.0]);
+        write_libsvm(&path, &data).unwrap();
+        assert_eq!(read_libsvm::<f64>(&path).unwrap().x.ncols(), 2);
+        let options = ReadOptions {
+            libsvm_features: Some(2),
+            ..ReadOptions::default()
+        };
+        let read = read_libsvm_with::<f64>(&path, &options).unwrap();
+        assert_eq!(read.x, data.x);
+        assert_eq!(read.feature_names, vec![INTERCEPT, "x_1", "x_2"]);
+
+        // features beyond the count are errors
+        let options = ReadOptions {
+            libsvm_features: Some(0),
+            ..options
+        };
+        assert!(matches!(
+            read_libsvm_with::<f64>(&path, &options),
+            Err(TrainingDataError::Syntax { line: 1, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn sample_data() -> TrainingData<f64> {
+        TrainingData::new(array![[1.0, 1.5, -1.0], [1.0, 0.0, 2.0]], array![3.0, 0.25])
+    }
+}
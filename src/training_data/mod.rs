@@ -0,0 +1,1365 @@
+pub mod jsonl;
+pub mod libsvm;
+pub mod split;
+
+pub use crate::training_data::jsonl::{read_jsonl, read_jsonl_with, write_jsonl};
+pub use crate::training_data::libsvm::{read_libsvm, read_libsvm_with, write_libsvm};
+pub use crate::training_data::split::SplitError;
+
+use csv::ReaderBuilder;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::array;
+use crate::preprocessing::{
+    imputer, CategoryEncoder, Encoding, ImputeStrategy, Imputer, PreprocessingError,
+    UnknownCategory,
+};
+
+// name of the column of ones prepended to x when an intercept is requested
+pub const INTERCEPT: &str = "intercept";
+
+#[derive(Debug, Clone)]
+pub struct TrainingData<T> {
+    pub x: array::Array2<T>,
+    pub y: array::Array1<T>,
+    // one name per column of x
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+    // fill values for the features when the data was loaded with imputation
+    pub imputer: Option<Imputer<T>>,
+    // vocabularies of the categorical features, in the order of their columns in x
+    pub encoders: Vec<CategoryEncoder>,
+}
+
+impl<T> TrainingData<T> {
+    // names the features like default_feature_names and the target y
+    pub fn new(x: array::Array2<T>, y: array::Array1<T>) -> Self
+    where
+        T: num_traits::One + PartialEq,
+    {
+        let feature_names = default_feature_names(&x.view());
+        Self {
+            x,
+            y,
+            feature_names,
+            target_name: "y".to_string(),
+            imputer: None,
+            encoders: Vec::new(),
+        }
+    }
+
+    pub fn feature_index(&self, name: &str) -> Option<usize> {
+        self.feature_names
+            .iter()
+            .position(|feature| feature == name)
+    }
+
+    // Encodes a new sample like the rows of x were, e.g. to predict on it.
+    // row holds one value per feature column of the file in their order,
+    // without the target, and None for a missing value. Categories go
+    // through the saved encoders and missing values through the saved imputer.
+    pub fn transform_row(&self, row: &[Option<&str>]) -> Result<Vec<T>, PreprocessingError>
+    where
+        T: num_traits::Float + std::str::FromStr,
+    {
+        let intercept = self.feature_names.first().map(String::as_str) == Some(INTERCEPT);
+        let encoded_width: usize = self.encoders.iter().map(CategoryEncoder::width).sum();
+        let expected =
+            self.feature_names.len() - intercept as usize - encoded_width + self.encoders.len();
+        if row.len() != expected {
+            return Err(PreprocessingError::ColumnCountMismatch {
+                expected,
+                found: row.len(),
+            });
+        }
+
+        let mut values = Vec::with_capacity(self.feature_names.len());
+        if intercept {
+            values.push(T::one());
+        }
+        let mut encoders = self.encoders.iter().peekable();
+        for cell in row.iter().map(|cell| cell.map(str::trim)) {
+            let name = &self.feature_names[values.len()];
+            match encoders.next_if(|encoder| encoder.feature_names().first() == Some(name)) {
+                Some(encoder) => match cell {
+                    Some(text) => encoder.encode_into(text, &mut values)?,
+                    None => encoder.encode_missing_into(&mut values),
+                },
+                None => values.push(match cell {
+                    Some(text) => {
+                        text.parse::<T>()
+                            .map_err(|_| PreprocessingError::InvalidNumber {
+                                feature: name.clone(),
+                                text: text.to_string(),
+                            })?
+                    }
+                    None => T::nan(),
+                }),
+            }
+        }
+
+        match &self.imputer {
+            Some(imputer) => {
+                let mut x = array::Array2::from_shape_vec((1, values.len()), values)
+                    .expect("one value per feature");
+                imputer.transform(&mut x)?;
+                Ok(x.into_iter().copied().collect())
+            }
+            None => match values.iter().position(|value| value.is_nan()) {
+                Some(j) => Err(PreprocessingError::MissingValue {
+                    feature: self.feature_names[j].clone(),
+                    row: 0,
+                }),
+                None => Ok(values),
+            },
+        }
+    }
+}
+
+// Names the columns of x x_1, x_2, ... A leading column of ones is named
+// INTERCEPT like the one read_data prepends and is not counted.
+pub(crate) fn default_feature_names<T>(x: &array::ArrayView2<T>) -> Vec<String>
+where
+    T: num_traits::One + PartialEq,
+{
+    let intercept = x.ncols() > 0 && x.nrows() > 0 && x.column(0).into_iter().all(|v| v.is_one());
+    intercept
+        .then(|| INTERCEPT.to_string())
+        .into_iter()
+        .chain((1..=x.ncols() - intercept as usize).map(|i| format!("x_{}", i)))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoricalColumn {
+    pub column: Column,
+    pub encoding: Encoding,
+    // learned from the data in sorted order when not given
+    pub vocabulary: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    // the last column when not set
+    pub target: Option<Column>,
+    // every column except the target when not set, in file order otherwise in the given order
+    pub features: Option<Vec<Column>>,
+    // prepends a column of ones named INTERCEPT to x
+    pub intercept: bool,
+    // cells that equal one of these after trimming are missing
+    pub missing_values: Vec<String>,
+    // how missing values are handled per column, columns that are not listed
+    // use the default and a missing value without strategy is an error
+    pub imputation: Vec<(Column, ImputeStrategy)>,
+    pub default_imputation: Option<ImputeStrategy>,
+    // string feature columns that are encoded to numeric features
+    pub categorical: Vec<CategoricalColumn>,
+    // encoding of the unlisted feature columns that hold values which do not parse as numbers
+    pub detect_categorical: Option<Encoding>,
+    // applies to values outside of a given vocabulary
+    pub unknown_category: UnknownCategory,
+    // whether the first row names the columns, detected when not set: a first row
+    // whose cells are all numbers or missing is data. Without header the columns
+    // are named x_1, x_2, ... and the last one y
+    pub has_header: Option<bool>,
+    pub delimiter: u8,
+    // lines starting with this byte are skipped
+    pub comment: Option<u8>,
+    // number of features of a LIBSVM file, which cannot tell features that are
+    // 0 in every row from features that do not exist. The largest feature index
+    // found when not set
+    pub libsvm_features: Option<usize>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            target: None,
+            features: None,
+            intercept: true,
+            missing_values: vec!["".to_string(), "NA".to_string()],
+            imputation: Vec::new(),
+            default_imputation: None,
+            categorical: Vec::new(),
+            detect_categorical: None,
+            unknown_category: UnknownCategory::Error,
+            has_header: None,
+            delimiter: b',',
+            comment: None,
+            libsvm_features: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TrainingDataError {
+    // the file could not be opened
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    // malformed input that the csv reader rejected, e.g. invalid utf8
+    Csv {
+        path: PathBuf,
+        line: Option<u64>,
+        source: csv::Error,
+    },
+    Parse {
+        path: PathBuf,
+        line: u64,
+        column: String,
+        text: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    // a line that is not valid JSON or LIBSVM
+    Syntax {
+        path: PathBuf,
+        line: u64,
+        message: String,
+    },
+    // a row with a different number of fields than the header
+    RaggedRow {
+        path: PathBuf,
+        line: u64,
+        expected: usize,
+        found: usize,
+    },
+    ColumnNotFound {
+        path: PathBuf,
+        column: Column,
+    },
+    TargetIsFeature {
+        path: PathBuf,
+        column: String,
+    },
+    // a missing value in a column without imputation strategy
+    MissingValue {
+        path: PathBuf,
+        line: u64,
+        column: String,
+    },
+    // a failed encoding or imputation, with the line when it concerns a single row
+    Preprocessing {
+        path: PathBuf,
+        line: Option<u64>,
+        source: PreprocessingError,
+    },
+    // the file holds no samples
+    EmptyDataset {
+        path: PathBuf,
+    },
+    // a sparse file whose samples would not fit in memory as a dense x
+    TooLarge {
+        path: PathBuf,
+        rows: usize,
+        columns: usize,
+    },
+    // an option that needs the whole file at once, given to a streaming reader
+    UnsupportedOption {
+        path: PathBuf,
+        option: &'static str,
+    },
+}
+
+impl TrainingDataError {
+    pub(crate) fn from_csv(path: &Path, error: csv::Error) -> Self {
+        let path = path.to_path_buf();
+        match *error.kind() {
+            csv::ErrorKind::UnequalLengths {
+                ref pos,
+                expected_len,
+                len,
+            } => TrainingDataError::RaggedRow {
+                path,
+                line: pos.as_ref().map_or(0, |position| position.line()),
+                expected: expected_len as usize,
+                found: len as usize,
+            },
+            _ => TrainingDataError::Csv {
+                path,
+                line: error.position().map(|position| position.line()),
+                source: error,
+            },
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            TrainingDataError::Io { path, .. }
+            | TrainingDataError::Csv { path, .. }
+            | TrainingDataError::Parse { path, .. }
+            | TrainingDataError::Syntax { path, .. }
+            | TrainingDataError::RaggedRow { path, .. }
+            | TrainingDataError::ColumnNotFound { path, .. }
+            | TrainingDataError::TargetIsFeature { path, .. }
+            | TrainingDataError::MissingValue { path, .. }
+            | TrainingDataError::Preprocessing { path, .. }
+            | TrainingDataError::EmptyDataset { path }
+            | TrainingDataError::TooLarge { path, .. }
+            | TrainingDataError::UnsupportedOption { path, .. } => path,
+        }
+    }
+
+    // 1-based line of the offending row, when the error is tied to one
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            TrainingDataError::Csv { line, .. } | TrainingDataError::Preprocessing { line, .. } => {
+                *line
+            }
+            TrainingDataError::Parse { line, .. }
+            | TrainingDataError::Syntax { line, .. }
+            | TrainingDataError::RaggedRow { line, .. }
+            | TrainingDataError::MissingValue { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Index(index) => write!(f, "#{}", index),
+            Column::Name(name) => write!(f, "'{}'", name),
+        }
+    }
+}
+
+impl fmt::Display for TrainingDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path().display())?;
+        if let Some(line) = self.line() {
+            write!(f, ":{}", line)?;
+        }
+        match self {
+            TrainingDataError::Io { source, .. } => write!(f, ": {}", source),
+            TrainingDataError::Csv { source, .. } => write!(f, ": {}", source),
+            TrainingDataError::Parse {
+                column,
+                text,
+                source,
+                ..
+            } => write!(
+                f,
+                ": column '{}': cannot parse '{}' as a number: {}",
+                column, text, source
+            ),
+            TrainingDataError::Syntax { message, .. } => write!(f, ": {}", message),
+            TrainingDataError::RaggedRow {
+                expected, found, ..
+            } => write!(f, ": expected {} fields, found {}", expected, found),
+            TrainingDataError::ColumnNotFound { column, .. } => {
+                write!(f, ": no column {}", column)
+            }
+            TrainingDataError::TargetIsFeature { column, .. } => {
+                write!(
+                    f,
+                    ": target column '{}' is also selected as a feature",
+                    column
+                )
+            }
+            TrainingDataError::MissingValue { column, .. } => {
+                write!(f, ": missing value in column '{}'", column)
+            }
+            TrainingDataError::Preprocessing { source, .. } => write!(f, ": {}", source),
+            TrainingDataError::EmptyDataset { .. } => write!(f, ": no samples"),
+            TrainingDataError::TooLarge { rows, columns, .. } => {
+                write!(
+                    f,
+                    ": {} rows of {} features are too large for x",
+                    rows, columns
+                )
+            }
+            TrainingDataError::UnsupportedOption { option, .. } => {
+                write!(f, ": option '{}' is not supported when streaming", option)
+            }
+        }
+    }
+}
+
+impl Error for TrainingDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TrainingDataError::Io { source, .. } => Some(source),
+            TrainingDataError::Csv { source, .. } => Some(source),
+            TrainingDataError::Parse { source, .. } => Some(source.as_ref()),
+            TrainingDataError::Preprocessing { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub fn read_data<T>(path: impl AsRef<Path>) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    read_data_with(path, &ReadOptions::default())
+}
+
+pub fn read_data_with<T>(
+    path: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|source| TrainingDataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut reader = csv_reader(bytes.as_slice(), options);
+    let rows: Vec<RawRow> = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|error| TrainingDataError::from_csv(path, error))?;
+            Ok(RawRow::dense(
+                record.position().map_or(0, |position| {
+                    record_line(
+                        &bytes[position.byte() as usize..],
+                        position,
+                        options.comment,
+                    )
+                }),
+                record.iter().map(|cell| Some(cell.to_string())).collect(),
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    build(path, RawTable::split_header::<T>(rows, options), options)
+}
+
+// the header is handled by the caller, so that it can be told apart from data
+pub(crate) fn csv_reader<R: std::io::Read>(reader: R, options: &ReadOptions) -> csv::Reader<R> {
+    ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(options.delimiter)
+        .comment(options.comment)
+        .from_reader(reader)
+}
+
+// csv reports the position a record started searching from, which is before
+// the blank and comment lines that precede it. rest starts at that position
+pub(crate) fn record_line(
+    mut rest: impl std::io::BufRead,
+    position: &csv::Position,
+    comment: Option<u8>,
+) -> u64 {
+    let mut line = position.line();
+    let mut text = Vec::new();
+    while rest.read_until(b'\n', &mut text).unwrap_or(0) > 0 {
+        let skipped = matches!(text.as_slice(), b"\n" | b"\r\n")
+            || comment.is_some_and(|comment| text.first() == Some(&comment));
+        if !skipped {
+            break;
+        }
+        line += 1;
+        text.clear();
+    }
+    line
+}
+
+// whether the first row of a file names its columns, None is a missing cell
+pub(crate) fn has_header<'a, T: std::str::FromStr>(
+    first_row: impl IntoIterator<Item = Option<&'a str>>,
+    options: &ReadOptions,
+) -> bool {
+    options.has_header.unwrap_or_else(|| {
+        let is_missing = |text: &str| options.missing_values.iter().any(|marker| marker == text);
+        !first_row.into_iter().all(|cell| {
+            cell.map(str::trim)
+                .is_none_or(|cell| is_missing(cell) || cell.parse::<T>().is_ok())
+        })
+    })
+}
+
+// names of the columns of a file without header
+pub(crate) fn unnamed_columns(count: usize) -> Vec<String> {
+    (1..count)
+        .map(|i| format!("x_{}", i))
+        .chain(std::iter::once("y".to_string()))
+        .collect()
+}
+
+// cells of a file as text, before any column is interpreted. None is a cell
+// that is missing whatever the missing value markers, e.g. a JSON null
+struct RawTable {
+    header: Vec<String>,
+    rows: Vec<RawRow>,
+}
+
+struct RawRow {
+    line: u64,
+    cells: Cells,
+}
+
+enum Cells {
+    Dense(Vec<Option<String>>),
+    // (column, text) of the cells a sparse format lists, sorted by column.
+    // The other cells up to len are 0
+    Sparse {
+        len: usize,
+        cells: Vec<(usize, String)>,
+    },
+}
+
+impl RawRow {
+    fn dense(line: u64, cells: Vec<Option<String>>) -> Self {
+        RawRow {
+            line,
+            cells: Cells::Dense(cells),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.cells {
+            Cells::Dense(cells) => cells.len(),
+            Cells::Sparse { len, .. } => *len,
+        }
+    }
+
+    fn cell(&self, column: usize) -> Option<&str> {
+        match &self.cells {
+            Cells::Dense(cells) => cells[column].as_deref(),
+            Cells::Sparse { cells, .. } => Some(
+                match cells.binary_search_by_key(&column, |(column, _)| *column) {
+                    Ok(i) => cells[i].1.as_str(),
+                    Err(_) => "0",
+                },
+            ),
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = Option<&str>> {
+        (0..self.len()).map(|column| self.cell(column))
+    }
+}
+
+impl RawTable {
+    fn split_header<T: std::str::FromStr>(mut rows: Vec<RawRow>, options: &ReadOptions) -> Self {
+        let Some(first) = rows.first() else {
+            return RawTable {
+                header: Vec::new(),
+                rows,
+            };
+        };
+        let header = if has_header::<T>(first.cells(), options) {
+            rows.remove(0)
+                .cells()
+                .map(|name| name.unwrap_or_default().trim().to_string())
+                .collect()
+        } else {
+            unnamed_columns(first.len())
+        };
+        RawTable { header, rows }
+    }
+}
+
+// how one selected column of the file becomes columns of x
+enum FeatureColumn {
+    Numeric,
+    Categorical(CategoryEncoder),
+}
+
+fn build<T>(
+    path: &Path,
+    table: RawTable,
+    options: &ReadOptions,
+) -> Result<TrainingData<T>, TrainingDataError>
+where
+    T: num_traits::Float + std::str::FromStr,
+    <T as std::str::FromStr>::Err: Error + Send + Sync + 'static,
+{
+    let RawTable { header, rows } = table;
+    if header.is_empty() {
+        return Err(TrainingDataError::EmptyDataset {
+            path: path.to_path_buf(),
+        });
+    }
+    let (target, features) = select_columns(&header, options).map_err(|error| error.at(path))?;
+    let mut strategies = vec![options.default_imputation; header.len()];
+    let mut explicit_strategies = vec![false; header.len()];
+    for (column, strategy) in &options.imputation {
+        let index = resolve_column(&header, column).map_err(|error| error.at(path))?;
+        strategies[index] = Some(*strategy);
+        explicit_strategies[index] = true;
+    }
+    let mut categorical = vec![None; header.len()];
+    for spec in &options.categorical {
+        let index = resolve_column(&header, &spec.column).map_err(|error| error.at(path))?;
+        categorical[index] = Some(spec);
+    }
+
+    let is_marker = |text: &str| {
+        options
+            .missing_values
+            .iter()
+            .any(|marker| marker == text.trim())
+    };
+    let is_missing = |cell: Option<&str>| cell.is_none_or(is_marker);
+    let mut kept = Vec::with_capacity(rows.len());
+    'rows: for row in &rows {
+        for &column in features.iter().chain(std::iter::once(&target)) {
+            if is_missing(row.cell(column)) {
+                match strategies[column] {
+                    None => {
+                        return Err(TrainingDataError::MissingValue {
+                            path: path.to_path_buf(),
+                            line: row.line,
+                            column: header[column].clone(),
+                        })
+                    }
+                    Some(ImputeStrategy::DropRow) => continue 'rows,
+                    // filled in once the statistics of the whole column are known
+                    Some(_) => {}
+                }
+            }
+        }
+        kept.push(row);
+    }
+    if kept.is_empty() {
+        return Err(TrainingDataError::EmptyDataset {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let observed = |column: usize| {
+        kept.iter()
+            .filter_map(move |row| row.cell(column))
+            .map(str::trim)
+            .filter(move |&text| !is_marker(text))
+    };
+    let feature_columns: Vec<FeatureColumn> = features
+        .iter()
+        .map(
+            |&column| match (categorical[column], options.detect_categorical) {
+                (Some(spec), _) => FeatureColumn::Categorical(match &spec.vocabulary {
+                    Some(vocabulary) => CategoryEncoder::with_vocabulary(
+                        &header[column],
+                        vocabulary.clone(),
+                        spec.encoding,
+                        options.unknown_category,
+                    ),
+                    None => CategoryEncoder::fit(
+                        &header[column],
+                        observed(column),
+                        spec.encoding,
+                        options.unknown_category,
+                    ),
+                }),
+                (None, Some(encoding))
+                    if observed(column).any(|text| text.parse::<T>().is_err()) =>
+                {
+                    FeatureColumn::Categorical(CategoryEncoder::fit(
+                        &header[column],
+                        observed(column),
+                        encoding,
+                        options.unknown_category,
+                    ))
+                }
+                _ => FeatureColumn::Numeric,
+            },
+        )
+        .collect();
+
+    let mut feature_names = Vec::new();
+    let mut feature_strategies = Vec::new();
+    if options.intercept {
+        feature_names.push(INTERCEPT.to_string());
+        feature_strategies.push(None);
+    }
+    for (&column, feature) in features.iter().zip(&feature_columns) {
+        match feature {
+            FeatureColumn::Categorical(encoder) if encoder.encoding() == Encoding::OneHot => {
+                feature_strategies.extend(
+                    one_hot_strategies(
+                        encoder,
+                        strategies[column],
+                        explicit_strategies[column],
+                        observed(column),
+                    )
+                    .map_err(|source| TrainingDataError::Preprocessing {
+                        path: path.to_path_buf(),
+                        line: None,
+                        source,
+                    })?,
+                );
+                feature_names.extend(encoder.feature_names());
+            }
+            FeatureColumn::Categorical(encoder) => {
+                feature_strategies.push(strategies[column]);
+                feature_names.extend(encoder.feature_names());
+            }
+            FeatureColumn::Numeric => {
+                feature_strategies.push(strategies[column]);
+                feature_names.push(header[column].clone());
+            }
+        }
+    }
+
+    let parse = |row: &RawRow, column: usize| {
+        let Some(text) = row.cell(column).filter(|&text| !is_marker(text)) else {
+            return Ok(T::nan());
+        };
+        text.trim()
+            .parse::<T>()
+            .map_err(|source| TrainingDataError::Parse {
+                path: path.to_path_buf(),
+                line: row.line,
+                column: header[column].clone(),
+                text: text.to_string(),
+                source: Box::new(source),
+            })
+    };
+    let mut x_values: Vec<T> = Vec::with_capacity(kept.len() * feature_names.len());
+    let mut y_values: Vec<T> = Vec::with_capacity(kept.len());
+    // line of every row of x
+    let mut lines = Vec::with_capacity(kept.len());
+    'encode: for row in &kept {
+        let start = x_values.len();
+        if options.intercept {
+            x_values.push(T::one());
+        }
+        for (&column, feature) in features.iter().zip(&feature_columns) {
+            match feature {
+                FeatureColumn::Numeric => x_values.push(parse(row, column)?),
+                FeatureColumn::Categorical(encoder) if is_missing(row.cell(column)) => {
+                    encoder.encode_missing_into(&mut x_values)
+                }
+                FeatureColumn::Categorical(encoder) => {
+                    encoder
+                        .encode_into(row.cell(column).unwrap_or_default().trim(), &mut x_values)
+                        .map_err(|source| TrainingDataError::Preprocessing {
+                            path: path.to_path_buf(),
+                            line: Some(row.line),
+                            source,
+                        })?;
+                    // an unknown category encoded as missing is treated like a missing cell
+                    let encoded = &x_values[x_values.len() - encoder.width()..];
+                    if encoded.iter().any(|value| value.is_nan()) {
+                        match strategies[column] {
+                            None => {
+                                return Err(TrainingDataError::MissingValue {
+                                    path: path.to_path_buf(),
+                                    line: row.line,
+                                    column: header[column].clone(),
+                                })
+                            }
+                            Some(ImputeStrategy::DropRow) => {
+                                x_values.truncate(start);
+                                continue 'encode;
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                }
+            }
+        }
+        y_values.push(parse(row, target)?);
+        lines.push(row.line);
+    }
+    if lines.is_empty() {
+        return Err(TrainingDataError::EmptyDataset {
+            path: path.to_path_buf(),
+        });
+    }
+    let mut x = array::Array2::from_shape_vec((lines.len(), feature_names.len()), x_values)
+        .expect("every row holds one value per feature");
+
+    // errors of a row of x are located at the line the row was read from
+    let preprocessing_error = |source| TrainingDataError::Preprocessing {
+        path: path.to_path_buf(),
+        line: match &source {
+            PreprocessingError::MissingValue { row, .. } => lines.get(*row).copied(),
+            _ => None,
+        },
+        source,
+    };
+    let imputer = if strategies.iter().any(Option::is_some) {
+        let imputer =
+            Imputer::fit(&x, &feature_names, &feature_strategies).map_err(preprocessing_error)?;
+        imputer.transform(&mut x).map_err(preprocessing_error)?;
+        Some(imputer)
+    } else {
+        None
+    };
+    if let Some(strategy) = strategies[target] {
+        if let Some(fill_value) = imputer::statistic(y_values.clone(), strategy) {
+            y_values
+                .iter_mut()
+                .filter(|value| value.is_nan())
+                .for_each(|value| *value = fill_value);
+        } else if y_values.iter().any(|value| value.is_nan()) {
+            return Err(preprocessing_error(PreprocessingError::NoObservedValues {
+                feature: header[target].clone(),
+            }));
+        }
+    }
+
+    Ok(TrainingData {
+        x,
+        y: array::Array1::from_vec(y_values),
+        feature_names,
+        target_name: header[target].clone(),
+        imputer,
+        encoders: feature_columns
+            .into_iter()
+            .filter_map(|feature| match feature {
+                FeatureColumn::Categorical(encoder) => Some(encoder),
+                FeatureColumn::Numeric => None,
+            })
+            .collect(),
+    })
+}
+
+// Strategies of the columns of a one-hot encoded feature. Imputing those columns
+// one by one could give rows that are no valid encoding, so a missing category is
+// filled with the most frequent one as a whole. Mean, median and constant given
+// for the feature are refused, as the default they fall back to the most frequent category.
+fn one_hot_strategies<'a>(
+    encoder: &CategoryEncoder,
+    strategy: Option<ImputeStrategy>,
+    explicit: bool,
+    observed: impl Iterator<Item = &'a str>,
+) -> Result<Vec<Option<ImputeStrategy>>, PreprocessingError> {
+    match strategy {
+        None | Some(ImputeStrategy::DropRow) => Ok(vec![strategy; encoder.width()]),
+        Some(strategy) if explicit && strategy != ImputeStrategy::MostFrequent => {
+            Err(PreprocessingError::UnsupportedStrategy {
+                feature: encoder.feature().to_string(),
+                strategy,
+            })
+        }
+        Some(_) => match encoder.most_frequent(observed) {
+            Some(category) => Ok((0..encoder.width())
+                .map(|i| {
+                    Some(ImputeStrategy::Constant(if i == category {
+                        1.0
+                    } else {
+                        0.0
+                    }))
+                })
+                .collect()),
+            None => Err(PreprocessingError::NoObservedValues {
+                feature: encoder.feature().to_string(),
+            }),
+        },
+    }
+}
+
+// column selection problems, located in a file by the caller
+pub(crate) enum SelectionError {
+    ColumnNotFound(Column),
+    TargetIsFeature(String),
+}
+
+impl SelectionError {
+    pub(crate) fn at(self, path: &Path) -> TrainingDataError {
+        let path = path.to_path_buf();
+        match self {
+            SelectionError::ColumnNotFound(column) => {
+                TrainingDataError::ColumnNotFound { path, column }
+            }
+            SelectionError::TargetIsFeature(column) => {
+                TrainingDataError::TargetIsFeature { path, column }
+            }
+        }
+    }
+}
+
+fn resolve_column(header: &[String], column: &Column) -> Result<usize, SelectionError> {
+    match column {
+        Column::Index(index) => Some(*index).filter(|&index| index < header.len()),
+        Column::Name(name) => header.iter().position(|column| column == name),
+    }
+    .ok_or_else(|| SelectionError::ColumnNotFound(column.clone()))
+}
+
+// resolves the target and feature columns to indices into the header
+pub(crate) fn select_columns(
+    header: &[String],
+    options: &ReadOptions,
+) -> Result<(usize, Vec<usize>), SelectionError> {
+    let target = match &options.target {
+        Some(column) => resolve_column(header, column)?,
+        None => header
+            .len()
+            .checked_sub(1)
+            .ok_or(SelectionError::ColumnNotFound(Column::Index(0)))?,
+    };
+    let features = match &options.features {
+        Some(columns) => columns
+            .iter()
+            .map(|column| resolve_column(header, column))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => (0..header.len()).filter(|&i| i != target).collect(),
+    };
+    if features.contains(&target) {
+        return Err(SelectionError::TargetIsFeature(header[target].clone()));
+    }
+    Ok((target, features))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) fn write_temp(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("ai_playground_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_default_options() {
+        let data = read_data::<f64>("resources/4.csv").unwrap();
+        assert_eq!(
+            data.feature_names,
+            vec![INTERCEPT, "x_1", "x_2", "x_3", "x_4"]
+        );
+        assert_eq!(data.target_name, "y");
+        assert_eq!(data.x.ncols(), 5);
+        assert_eq!(data.x.nrows(), data.y.len());
+        assert_eq!(data.x.column(0).sum(), data.y.len() as f64);
+        assert_eq!(data.y[0], 12001739.0);
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let options = ReadOptions {
+            target: Some("x_3".into()),
+            features: Some(vec!["y".into(), 0.into()]),
+            intercept: false,
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>("resources/4.csv", &options).unwrap();
+        assert_eq!(data.feature_names, vec!["y", "x_1"]);
+        assert_eq!(data.target_name, "x_3");
+        assert_eq!(data.x.row(0), array![12001739.0, 0.0]);
+        assert_eq!(data.y[0], 0.8366785645484924);
+        assert_eq!(data.feature_index("x_1"), Some(1));
+
+        let options = ReadOptions {
+            target: Some(1.into()),
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>("resources/4.csv", &options).unwrap();
+        assert_eq!(data.target_name, "x_2");
+        assert_eq!(
+            data.feature_names,
+            vec![INTERCEPT, "x_1", "x_3", "x_4", "y"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_columns() {
+        let options = ReadOptions {
+            target: Some("z".into()),
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            read_data_with::<f64>("resources/4.csv", &options),
+            Err(TrainingDataError::ColumnNotFound {
+                column: Column::Name(_),
+                ..
+            })
+        ));
+        let options = ReadOptions {
+            features: Some(vec![5.into()]),
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            read_data_with::<f64>("resources/4.csv", &options),
+            Err(TrainingDataError::ColumnNotFound {
+                column: Column::Index(5),
+                ..
+            })
+        ));
+        let options = ReadOptions {
+            features: Some(vec!["x_1".into(), "y".into()]),
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            read_data_with::<f64>("resources/4.csv", &options),
+            Err(TrainingDataError::TargetIsFeature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_located_errors() {
+        let path = write_temp("parse.csv", "x_1,y\n1.0,2.0\n3.0,abc\n");
+        let error = read_data::<f64>(&path).unwrap_err();
+        match &error {
+            TrainingDataError::Parse {
+                line, column, text, ..
+            } => {
+                assert_eq!(*line, 3);
+                assert_eq!(column, "y");
+                assert_eq!(text, "abc");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+        assert!(error.source().is_some());
+        assert!(error.to_string().ends_with(
+            "parse.csv:3: column 'y': cannot parse 'abc' as a number: invalid float literal"
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        let path = write_temp("ragged.csv", "x_1,y\n1.0,2.0\n3.0\n");
+        assert!(matches!(
+            read_data::<f64>(&path),
+            Err(TrainingDataError::RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        let error = read_data::<f64>("resources/missing.csv").unwrap_err();
+        assert!(matches!(error, TrainingDataError::Io { .. }));
+        assert_eq!(error.path(), Path::new("resources/missing.csv"));
+    }
+
+    #[test]
+    fn test_empty_dataset() {
+        for (name, contents) in [("empty.csv", ""), ("header_only.csv", "x_1,y\n")] {
+            let path = write_temp(name, contents);
+            assert!(matches!(
+                read_data::<f64>(&path),
+                Err(TrainingDataError::EmptyDataset { .. })
+            ));
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_missing_values() {
+        let path = write_temp(
+            "missing.csv",
+            "x_1,x_2,y\n1.0,NA,1.0\n,4.0,2.0\n3.0,6.0, \n5.0,8.0,4.0\n",
+        );
+        let error = read_data::<f64>(&path).unwrap_err();
+        assert!(matches!(
+            &error,
+            TrainingDataError::MissingValue { line: 2, column, .. } if column == "x_2"
+        ));
+
+        let options = ReadOptions {
+            imputation: vec![
+                ("x_1".into(), ImputeStrategy::Mean),
+                (1.into(), ImputeStrategy::Median),
+                ("y".into(), ImputeStrategy::DropRow),
+            ],
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(
+            data.x,
+            array![[1.0, 1.0, 6.0], [1.0, 3.0, 4.0], [1.0, 5.0, 8.0]]
+        );
+        assert_eq!(data.y, array![1.0, 2.0, 4.0]);
+        let imputer = data.imputer.unwrap();
+        assert_eq!(imputer.fill_values(), &[None, Some(3.0), Some(6.0)]);
+        assert_eq!(imputer.feature_names(), data.feature_names.as_slice());
+
+        let options = ReadOptions {
+            default_imputation: Some(ImputeStrategy::DropRow),
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(data.x, array![[1.0, 5.0, 8.0]]);
+
+        let options = ReadOptions {
+            missing_values: vec!["NA".to_string()],
+            default_imputation: Some(ImputeStrategy::Constant(0.0)),
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            read_data_with::<f64>(&path, &options),
+            Err(TrainingDataError::Parse { line: 3, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        // a NaN the imputer must not fill is reported at its line, after dropped rows
+        let path = write_temp(
+            "unfilled.csv",
+            "x_1,x_2,y\n1.0,2.0,\n# note\n3.0,,4.0\nNaN,6.0,5.0\n",
+        );
+        let options = ReadOptions {
+            imputation: vec![
+                ("x_2".into(), ImputeStrategy::Mean),
+                ("y".into(), ImputeStrategy::DropRow),
+            ],
+            comment: Some(b'#'),
+            ..ReadOptions::default()
+        };
+        let error = read_data_with::<f64>(&path, &options).unwrap_err();
+        assert!(matches!(
+            error,
+            TrainingDataError::Preprocessing {
+                line: Some(5),
+                source: PreprocessingError::MissingValue { row: 1, .. },
+                ..
+            }
+        ));
+        assert_eq!(error.line(), Some(5));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_categorical_columns() {
+        let path = write_temp(
+            "categorical.csv",
+            "size,region,tier,y\n1.0,south,basic,1.0\n2.0,north,pro,2.0\n3.0,,plus,3.0\n4.0,north,basic,4.0\n",
+        );
+        assert!(matches!(
+            read_data::<f64>(&path),
+            Err(TrainingDataError::MissingValue { line: 4, .. })
+        ));
+
+        let options = ReadOptions {
+            categorical: vec![CategoricalColumn {
+                column: "tier".into(),
+                encoding: Encoding::Ordinal,
+                vocabulary: Some(vec!["basic".into(), "plus".into(), "pro".into()]),
+            }],
+            detect_categorical: Some(Encoding::OneHot),
+            imputation: vec![("region".into(), ImputeStrategy::MostFrequent)],
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(
+            data.feature_names,
+            vec![INTERCEPT, "size", "region=north", "region=south", "tier"]
+        );
+        assert_eq!(
+            data.x,
+            array![
+                [1.0, 1.0, 0.0, 1.0, 0.0],
+                [1.0, 2.0, 1.0, 0.0, 2.0],
+                [1.0, 3.0, 1.0, 0.0, 1.0],
+                [1.0, 4.0, 1.0, 0.0, 0.0]
+            ]
+        );
+        assert_eq!(data.encoders.len(), 2);
+        assert_eq!(data.encoders[0].vocabulary(), &["north", "south"]);
+
+        // new samples are encoded and imputed like the rows of the file
+        assert_eq!(
+            data.transform_row(&[Some("2.0"), Some("north"), Some("pro")]),
+            Ok(data.x.row(1).into_iter().copied().collect())
+        );
+        assert_eq!(
+            data.transform_row(&[Some("2.5"), None, Some("plus")]),
+            Ok(vec![1.0, 2.5, 1.0, 0.0, 1.0])
+        );
+        assert_eq!(
+            data.transform_row(&[None, Some("south"), Some("basic")]),
+            Err(PreprocessingError::MissingValue {
+                feature: "size".to_string(),
+                row: 0
+            })
+        );
+        assert!(matches!(
+            data.transform_row(&[Some("2.5"), Some("west"), Some("plus")]),
+            Err(PreprocessingError::UnknownCategory { .. })
+        ));
+        assert!(matches!(
+            data.transform_row(&[Some("big"), Some("south"), Some("plus")]),
+            Err(PreprocessingError::InvalidNumber { .. })
+        ));
+        assert_eq!(
+            data.transform_row(&[Some("2.5")]),
+            Err(PreprocessingError::ColumnCountMismatch {
+                expected: 3,
+                found: 1
+            })
+        );
+
+        let options = ReadOptions {
+            categorical: vec![CategoricalColumn {
+                column: 2.into(),
+                encoding: Encoding::OneHot,
+                vocabulary: Some(vec!["basic".into(), "pro".into()]),
+            }],
+            detect_categorical: Some(Encoding::Ordinal),
+            imputation: vec![("region".into(), ImputeStrategy::MostFrequent)],
+            ..ReadOptions::default()
+        };
+        let error = read_data_with::<f64>(&path, &options).unwrap_err();
+        assert!(matches!(
+            error,
+            TrainingDataError::Preprocessing {
+                line: Some(4),
+                source: PreprocessingError::UnknownCategory { .. },
+                ..
+            }
+        ));
+        assert_eq!(error.line(), Some(4));
+
+        let options = ReadOptions {
+            unknown_category: UnknownCategory::Ignore,
+            ..options
+        };
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(
+            data.feature_names,
+            vec![INTERCEPT, "size", "region", "tier=basic", "tier=pro"]
+        );
+        assert_eq!(data.x.column(2), array![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(data.x.column(3), array![1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(data.x.column(4), array![0.0, 1.0, 0.0, 0.0]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_one_hot_imputation() {
+        let path = write_temp(
+            "one_hot_missing.csv",
+            "size,region,y\n1.0,south,1.0\n,north,2.0\n3.0,,3.0\n4.0,south,4.0\n",
+        );
+        let options = ReadOptions {
+            detect_categorical: Some(Encoding::OneHot),
+            default_imputation: Some(ImputeStrategy::Mean),
+            ..ReadOptions::default()
+        };
+        // the missing region is the most frequent one, not the mean of every column
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(
+            data.x,
+            array![
+                [1.0, 1.0, 0.0, 1.0],
+                [1.0, 8.0 / 3.0, 1.0, 0.0],
+                [1.0, 3.0, 0.0, 1.0],
+                [1.0, 4.0, 0.0, 1.0]
+            ]
+        );
+        let mut new = array![[1.0, 2.0, f64::NAN, f64::NAN]];
+        data.imputer.unwrap().transform(&mut new).unwrap();
+        assert_eq!(new, array![[1.0, 2.0, 0.0, 1.0]]);
+
+        let options = ReadOptions {
+            imputation: vec![("region".into(), ImputeStrategy::Constant(0.0))],
+            ..options
+        };
+        assert!(matches!(
+            read_data_with::<f64>(&path, &options),
+            Err(TrainingDataError::Preprocessing {
+                source: PreprocessingError::UnsupportedStrategy { .. },
+                ..
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_ordinal_category() {
+        let path = write_temp("unknown.csv", "region,y\nnorth,1\nsouth,2\nwest,3\n");
+        for unknown_category in [UnknownCategory::Ignore, UnknownCategory::Missing] {
+            let options = ReadOptions {
+                categorical: vec![CategoricalColumn {
+                    column: "region".into(),
+                    encoding: Encoding::Ordinal,
+                    vocabulary: Some(vec!["north".into(), "south".into()]),
+                }],
+                unknown_category,
+                ..ReadOptions::default()
+            };
+            // without a strategy the NaN it is encoded to cannot stay in x
+            assert!(matches!(
+                read_data_with::<f64>(&path, &options),
+                Err(TrainingDataError::MissingValue { line: 4, .. })
+            ));
+
+            let options = ReadOptions {
+                imputation: vec![("region".into(), ImputeStrategy::DropRow)],
+                ..options
+            };
+            let data = read_data_with::<f64>(&path, &options).unwrap();
+            assert_eq!(data.x, array![[1.0, 0.0], [1.0, 1.0]]);
+            assert_eq!(data.y, array![1.0, 2.0]);
+
+            let options = ReadOptions {
+                imputation: vec![("region".into(), ImputeStrategy::Constant(-1.0))],
+                ..options
+            };
+            let data = read_data_with::<f64>(&path, &options).unwrap();
+            assert_eq!(data.x.column(1), array![0.0, 1.0, -1.0]);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_header_detection() {
+        let data = read_data::<f64>("resources/3.csv").unwrap();
+        assert_eq!(data.feature_names, vec![INTERCEPT, "x_1"]);
+        assert_eq!(data.target_name, "y");
+        assert_eq!(data.y.len(), 30);
+        assert_eq!(data.x.row(0), array![1.0, 1.2000000000000002]);
+
+        let options = ReadOptions {
+            has_header: Some(true),
+            ..ReadOptions::default()
+        };
+        let data = read_data_with::<f64>("resources/3.csv", &options).unwrap();
+        assert_eq!(data.feature_names, vec![INTERCEPT, "1.2000000000000002"]);
+        assert_eq!(data.y.len(), 29);
+
+        let path = write_temp(
+            "semicolon.csv",
+            "# exported data\nsize;price\n1,5;3\n# a note\n2,5;5\n",
+        );
+        let options = ReadOptions {
+            delimiter: b';',
+            comment: Some(b'#'),
+            intercept: false,
+            ..ReadOptions::default()
+        };
+        assert!(matches!(
+            read_data_with::<f64>(&path, &options),
+            Err(TrainingDataError::Parse { line: 3, .. })
+        ));
+        let path = write_temp(
+            "semicolon.csv",
+            "# exported data\nsize;price\n1.5;3\n# a note\n2.5;5\n",
+        );
+        let data = read_data_with::<f64>(&path, &options).unwrap();
+        assert_eq!(data.feature_names, vec!["size"]);
+        assert_eq!(data.x, array![[1.5], [2.5]]);
+        assert_eq!(data.y, array![3.0, 5.0]);
+
+        let options = ReadOptions {
+            has_header: Some(false),
+            ..options
+        };
+        assert!(matches!(
+            read_data_with::<f64>(&path, &options),
+            Err(TrainingDataError::Parse { line: 2, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
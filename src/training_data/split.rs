@@ -0,0 +1,238 @@
+use crate::array::Axis;
+use crate::random::{self, SplitMix64};
+use crate::training_data::TrainingData;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitError {
+    // fractions must be in (0, 1) and leave samples for training
+    InvalidFraction(f64),
+    // a part of the split would have no samples, e.g. for a tiny dataset
+    EmptySplit(&'static str),
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitError::InvalidFraction(fraction) => {
+                write!(f, "invalid split fraction {}", fraction)
+            }
+            SplitError::EmptySplit(part) => write!(f, "the {} set would be empty", part),
+        }
+    }
+}
+
+impl Error for SplitError {}
+
+// sizes of the held out parts of n samples, rounded to the nearest sample
+fn held_out_sizes(n: usize, fractions: &[f64]) -> Result<Vec<usize>, SplitError> {
+    for &fraction in fractions {
+        if !(fraction > 0.0 && fraction < 1.0) {
+            return Err(SplitError::InvalidFraction(fraction));
+        }
+    }
+    let total: f64 = fractions.iter().sum();
+    if total >= 1.0 {
+        return Err(SplitError::InvalidFraction(total));
+    }
+    Ok(fractions
+        .iter()
+        .map(|fraction| (n as f64 * fraction).round() as usize)
+        .collect())
+}
+
+impl<T> TrainingData<T>
+where
+    T: Clone,
+{
+    // the samples at indices, in that order, with the names and fitted
+    // preprocessing of self
+    pub fn select_rows(&self, indices: &[usize]) -> Self {
+        Self {
+            x: self.x.select(Axis(0), indices),
+            y: self.y.select(Axis(0), indices),
+            feature_names: self.feature_names.clone(),
+            target_name: self.target_name.clone(),
+            imputer: self.imputer.clone(),
+            encoders: self.encoders.clone(),
+        }
+    }
+
+    pub fn shuffled(&self, seed: u64) -> Self {
+        self.select_rows(&random::permutation(self.y.len(), seed))
+    }
+
+    // (train, test) with round(test_fraction * samples) test samples
+    pub fn train_test_split(
+        &self,
+        test_fraction: f64,
+        seed: u64,
+    ) -> Result<(Self, Self), SplitError> {
+        let mut parts = self.split(random::permutation(self.y.len(), seed), &[test_fraction])?;
+        let test = parts.pop().expect("one part per fraction");
+        Ok((
+            self.parts(parts.pop().expect("training part"), "training")?,
+            self.parts(test, "test")?,
+        ))
+    }
+
+    // (train, validation, test)
+    pub fn train_validation_test_split(
+        &self,
+        validation_fraction: f64,
+        test_fraction: f64,
+        seed: u64,
+    ) -> Result<(Self, Self, Self), SplitError> {
+        let mut parts = self.split(
+            random::permutation(self.y.len(), seed),
+            &[validation_fraction, test_fraction],
+        )?;
+        let test = parts.pop().expect("one part per fraction");
+        let validation = parts.pop().expect("one part per fraction");
+        Ok((
+            self.parts(parts.pop().expect("training part"), "training")?,
+            self.parts(validation, "validation")?,
+            self.parts(test, "test")?,
+        ))
+    }
+
+    // (train, test) that keep the proportion of every class of y, for
+    // classification targets. Each class is split on its own and keeps at
+    // least one sample for training. 0.0 and -0.0 are one class, as are all NaNs
+    pub fn stratified_train_test_split(
+        &self,
+        test_fraction: f64,
+        seed: u64,
+    ) -> Result<(Self, Self), SplitError>
+    where
+        T: num_traits::Float,
+    {
+        held_out_sizes(0, &[test_fraction])?;
+        let mut classes: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+        for (i, value) in self.y.into_iter().enumerate() {
+            let value = value.to_f64().unwrap_or(f64::NAN);
+            let class = if value.is_nan() {
+                f64::NAN
+            } else if value == 0.0 {
+                0.0
+            } else {
+                value
+            };
+            classes.entry(class.to_bits()).or_default().push(i);
+        }
+        let mut random = SplitMix64::new(seed);
+        let (mut train, mut test) = (Vec::new(), Vec::new());
+        for mut indices in classes.into_values() {
+            random.shuffle(&mut indices);
+            let mut parts = self.split(indices, &[test_fraction])?;
+            let mut class_test = parts.pop().expect("one part per fraction");
+            let mut class_train = parts.pop().expect("training part");
+            if class_train.is_empty() {
+                class_train.extend(class_test.pop());
+            }
+            test.extend(class_test);
+            train.extend(class_train);
+        }
+        // samples of one class would otherwise be next to each other
+        random.shuffle(&mut train);
+        random.shuffle(&mut test);
+        Ok((self.parts(train, "training")?, self.parts(test, "test")?))
+    }
+
+    // training indices first, then one part per fraction, taken from the end
+    fn split(&self, indices: Vec<usize>, fractions: &[f64]) -> Result<Vec<Vec<usize>>, SplitError> {
+        let sizes = held_out_sizes(indices.len(), fractions)?;
+        let mut rest = indices;
+        let mut parts = Vec::with_capacity(fractions.len() + 1);
+        for &size in sizes.iter().rev() {
+            let size = size.min(rest.len());
+            parts.push(rest.split_off(rest.len() - size));
+        }
+        parts.push(rest);
+        parts.reverse();
+        Ok(parts)
+    }
+
+    fn parts(&self, indices: Vec<usize>, name: &'static str) -> Result<Self, SplitError> {
+        if indices.is_empty() {
+            return Err(SplitError::EmptySplit(name));
+        }
+        Ok(self.select_rows(&indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array;
+
+    fn numbered(n: usize) -> TrainingData<f64> {
+        let x = array::Array2::from_shape_vec((n, 2), (0..2 * n).map(|i| i as f64).collect());
+        let y = array::Array1::from_vec((0..n).map(|i| i as f64).collect());
+        TrainingData::new(x.unwrap(), y)
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        let data = numbered(10);
+        let (train, test) = data.train_test_split(0.3, 7).unwrap();
+        assert_eq!((train.y.len(), test.y.len()), (7, 3));
+        let mut seen: Vec<f64> = train.y.into_iter().chain(&test.y).copied().collect();
+        seen.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(seen, (0..10).map(|i| i as f64).collect::<Vec<_>>());
+        // rows stay together
+        for i in 0..test.y.len() {
+            assert_eq!(test.x[(i, 0)], 2.0 * test.y[i]);
+        }
+        assert_eq!(data.train_test_split(0.3, 7).unwrap().1.y, test.y);
+        assert_eq!(
+            data.shuffled(3).y,
+            data.select_rows(&random::permutation(10, 3)).y
+        );
+
+        let (train, validation, test) = data.train_validation_test_split(0.2, 0.2, 7).unwrap();
+        assert_eq!((train.y.len(), validation.y.len(), test.y.len()), (6, 2, 2));
+        assert_eq!(
+            data.train_test_split(1.0, 7).unwrap_err(),
+            SplitError::InvalidFraction(1.0)
+        );
+        assert_eq!(
+            data.train_validation_test_split(0.5, 0.5, 7).unwrap_err(),
+            SplitError::InvalidFraction(1.0)
+        );
+        assert_eq!(
+            numbered(3).train_test_split(0.1, 7).unwrap_err(),
+            SplitError::EmptySplit("test")
+        );
+    }
+
+    #[test]
+    fn test_stratified_split() {
+        let mut data = numbered(12);
+        data.y = array![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+        let (train, test) = data.stratified_train_test_split(0.25, 1).unwrap();
+        let ones = |data: &TrainingData<f64>| data.y.into_iter().filter(|&&y| y == 1.0).count();
+        assert_eq!((train.y.len(), test.y.len()), (9, 3));
+        assert_eq!((ones(&train), ones(&test)), (3, 1));
+        assert_eq!(
+            data.stratified_train_test_split(0.25, 1).unwrap().1.x,
+            test.x
+        );
+
+        // -0.0 joins the class of 0.0 and the NaNs form one class
+        let mut data = numbered(6);
+        data.y = array![0.0, -0.0, f64::NAN, -f64::NAN, 2.0, 2.0];
+        let (train, test) = data.stratified_train_test_split(0.5, 3).unwrap();
+        assert_eq!((train.y.len(), test.y.len()), (3, 3));
+        assert_eq!(train.y.into_iter().filter(|y| y.is_nan()).count(), 1);
+
+        // a class with a single sample stays in training
+        let mut data = numbered(5);
+        data.y = array![0.0, 0.0, 0.0, 0.0, 1.0];
+        let (train, test) = data.stratified_train_test_split(0.6, 3).unwrap();
+        assert_eq!((ones(&train), ones(&test)), (1, 0));
+    }
+}